@@ -1,12 +1,13 @@
 use about_page::app::App;
 use about_page::route::LocalizedRoute;
 use env_logger::{Builder, Env};
-use log::{error, info};
+use log::{error, info, warn};
 use std::env;
 use std::error::Error;
 use yew_router::LanguageUtils;
-use yew_ssg::StaticSiteGenerator;
 use yew_ssg::config_loader::load_config;
+use yew_ssg::watch::SiteWatcher;
+use yew_ssg::{SsgError, StaticSiteGenerator};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -17,6 +18,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     // Load configuration from file
     let config_path = "config.yaml";
+
+    // Keep regenerating on config, template and asset changes
+    if env::args().any(|arg| arg == "--watch") {
+        SiteWatcher::new(config_path)?
+            .run::<LocalizedRoute, App>()
+            .await;
+        return Ok(());
+    }
+
     let config = match load_config(config_path) {
         Ok(config) => {
             info!("✅ Configuration loaded successfully");
@@ -28,9 +38,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 let base_url =
                     env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
                 info!("Using base URL: {}", base_url);
-                env::set_var("BASE_URL", base_url);
+                unsafe { env::set_var("BASE_URL", base_url) };
             }
-            return Err(e);
+            return Err(e.into());
         }
     };
 
@@ -38,9 +48,31 @@ async fn main() -> Result<(), Box<dyn Error>> {
     info!("🚀 Initializing static site generator...");
     let generator = StaticSiteGenerator::new(config)?;
 
+    // Render pages on request instead of building them
+    if env::args().any(|arg| arg == "--serve") {
+        generator
+            .serve::<LocalizedRoute, App>("127.0.0.1:8080")
+            .await?;
+        return Ok(());
+    }
+
+    // Plan the build to catch output collisions before writing anything
+    let plan = generator.plan::<LocalizedRoute>();
+    info!(
+        "🗂️ Planned {} pages with {} issues",
+        plan.pages.len(),
+        plan.issues.len()
+    );
+
+    // Copy the static files of `public`, renamed with content hashes
+    generator.copy_assets()?;
+
     // Generate Standard Routes
     info!("📄 Generating standard routes with localization...");
-    generator.generate::<LocalizedRoute, App>().await?;
+    let mut report = generator
+        .generate::<LocalizedRoute, App>()
+        .await
+        .or_else(SsgError::into_report)?;
 
     // Generate Parameterized Routes with different language contexts
     info!("📝 Generating parameterized routes with localization...");
@@ -56,11 +88,42 @@ async fn main() -> Result<(), Box<dyn Error>> {
         // This will execute with German as the current language
     });
 
-    generator
-        .generate_parameterized_routes::<LocalizedRoute, App>()
-        .await?;
+    report.merge(
+        generator
+            .generate_parameterized_routes::<LocalizedRoute, App>()
+            .await
+            .or_else(SsgError::into_report)?,
+    );
+
+    info!("🗺️ Writing sitemap...");
+    generator.generate_sitemap::<LocalizedRoute>()?;
+    generator.generate_site_files()?;
+    generator.generate_feeds::<LocalizedRoute>()?;
+    generator.generate_redirects()?;
+
+    for (route, warning) in report.warnings() {
+        warn!("⚠️ {}: {}", route, warning);
+    }
+    info!("🗜️ Compressing outputs...");
+    report.compressed = generator.compress_outputs()?;
+    report.save(&generator.config.output_dir)?;
+
+    info!("🔗 Checking links...");
+    generator.check_links()?;
+
+    for failure in &report.failures {
+        error!("❌ {}", failure);
+    }
+    let report = report.into_result()?;
 
-    info!("\n✅ Static site generation complete! Check the 'dist' directory.");
+    info!(
+        "\n✅ Static site generation complete! {} pages ({} bytes, {} compressed files saving {} bytes) in {:?}. Check the 'dist' directory.",
+        report.pages.len(),
+        report.total_bytes(),
+        report.compressed.len(),
+        report.compression_savings(),
+        report.total_time
+    );
 
     Ok(())
 }
@@ -23,7 +23,6 @@ fn language_button(props: &LanguageButtonProps) -> Html {
     };
 
     let on_click = {
-        let lang = lang;
         let callback = props.on_click.clone();
 
         Callback::from(move |_: MouseEvent| {
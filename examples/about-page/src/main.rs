@@ -1,5 +1,10 @@
 use about_page::app::App;
 
 fn main() {
+    // Reuse the prerendered markup of pages generated with `hydration: true`
+    #[cfg(feature = "hydration")]
+    yew_router::hydrate::<App>("app");
+
+    #[cfg(not(feature = "hydration"))]
     yew::Renderer::<App>::new().render();
 }
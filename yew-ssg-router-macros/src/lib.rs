@@ -49,7 +49,6 @@ use syn::{DeriveInput, Expr, LitStr, parse_macro_input};
 /// 2. Implementations of `LocalizedRoutable` and `Routable` traits for the wrapper
 ///
 /// 3. IntoEnumIterator implementation for integration with `strum`
-
 #[proc_macro_derive(LocalizedRoutable, attributes(localized))]
 pub fn derive_localized_routable(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
@@ -0,0 +1,273 @@
+//! Hydration of statically generated pages.
+//!
+//! The static site generator renders every page through [`SsgRoot`]. With hydration enabled
+//! it also embeds the page's [`SsgRenderContext`] as JSON (see [`CONTEXT_ELEMENT_ID`]).
+//! In the browser, `hydrate` (feature `hydration`) reads the context back and hydrates the
+//! same component tree, so the prerendered DOM is reused instead of being replaced.
+//! The data of a route data provider is embedded separately (see [`ROUTE_DATA_ELEMENT_ID`])
+//! and read back into the context as well.
+
+use crate::render_context::{SsgRenderContext, SsgRenderContextProvider};
+use yew::prelude::*;
+
+/// Id of the `<script type="application/json">` element holding the render context of a page
+pub const CONTEXT_ELEMENT_ID: &str = "yew-ssg-context";
+
+/// Id of the `<script type="application/json">` element holding the route data of a page
+pub const ROUTE_DATA_ELEMENT_ID: &str = "yew-ssg-route-data";
+
+/// Properties for the SsgRoot component
+#[derive(Properties, PartialEq)]
+pub struct SsgRootProps {
+    pub context: SsgRenderContext,
+}
+
+/// Wraps the application root so it renders inside the page's render context.
+///
+/// Used both by the static site generator and by `hydrate`, so the browser hydrates the
+/// component tree the page was rendered from.
+#[function_component(SsgRoot)]
+pub fn ssg_root<C>(props: &SsgRootProps) -> Html
+where
+    C: BaseComponent<Properties = ()> + 'static,
+{
+    html! {
+        <SsgRenderContextProvider context={props.context.clone()}>
+            <C />
+        </SsgRenderContextProvider>
+    }
+}
+
+/// Script element embedding the render context in a page
+#[cfg(feature = "serde")]
+pub fn context_script(context: &SsgRenderContext) -> String {
+    let json = serde_json::to_string(context).unwrap_or_else(|_| "{}".to_string());
+    json_script(CONTEXT_ELEMENT_ID, &json)
+}
+
+/// Script element embedding the route data (JSON) in a page
+pub fn route_data_script(json: &str) -> String {
+    json_script(ROUTE_DATA_ELEMENT_ID, json)
+}
+
+fn json_script(id: &str, json: &str) -> String {
+    // `<` only occurs inside JSON strings, so escaping it keeps `</script>` out of the data
+    format!(
+        r#"<script type="application/json" id="{}">{}</script>"#,
+        id,
+        json.replace('<', "\\u003c")
+    )
+}
+
+/// Read a render context embedded by [`context_script`]
+#[cfg(feature = "serde")]
+pub fn parse_context(json: &str) -> Option<SsgRenderContext> {
+    serde_json::from_str(json).ok()
+}
+
+/// Compare the component marker a page starts with (`<[name]>`) against the root component
+/// the browser hydrates. Only pages generated by debug builds name their components, so
+/// unnamed markers always match.
+#[cfg(any(all(feature = "hydration", debug_assertions), test))]
+fn root_marker_mismatch(marker: &str, expected: &str) -> Option<String> {
+    let Some(name) = marker
+        .strip_prefix("<[")
+        .and_then(|marker| marker.strip_suffix("]>"))
+    else {
+        return Some(format!(
+            "the page starts with `<!--{}-->` instead of a component, was it generated with `hydration` enabled?",
+            marker
+        ));
+    };
+    if name.is_empty() || name == expected {
+        None
+    } else {
+        Some(format!(
+            "the page was rendered from `{}`, but the browser hydrates `{}`",
+            name, expected
+        ))
+    }
+}
+
+#[cfg(feature = "hydration")]
+mod client {
+    use super::*;
+    #[cfg(debug_assertions)]
+    use std::cell::Cell;
+    use web_sys::{Element, Node, console};
+    use yew::{AppHandle, Renderer};
+
+    /// Explanation appended to panics raised while hydrating in debug builds
+    #[cfg(debug_assertions)]
+    const MISMATCH_HINT: &str = "This panic happened while hydrating a page generated by \
+        yew-ssg: the component tree rendered in the browser differs from the prerendered \
+        markup. Build the client with the `hydration` feature of yew-ssg-router, so it \
+        renders the same static router components as the generator, and do not render \
+        browser-only state (window size, local storage, ...) during the first render.";
+
+    #[cfg(debug_assertions)]
+    thread_local! {
+        static HYDRATING: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// Hydrate the page prerendered into the element with id `root_id`.
+    ///
+    /// The render context embedded by the static site generator is read back, so `C`
+    /// renders exactly as it did during generation. Pages without prerendered markup or
+    /// without a context (e.g. during `trunk serve`) are rendered from scratch instead,
+    /// using the browser location as path. Debug builds report mismatches between the page
+    /// and the client in the console.
+    pub fn hydrate<C>(root_id: &str) -> AppHandle<SsgRoot<C>>
+    where
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .expect("no document found");
+        let root = document
+            .get_element_by_id(root_id)
+            .unwrap_or_else(|| panic!("no element with id `{}` found", root_id));
+        let context = document
+            .get_element_by_id(CONTEXT_ELEMENT_ID)
+            .and_then(|script| script.text_content())
+            .and_then(|json| parse_context(&json))
+            .map(|context| {
+                let data = document
+                    .get_element_by_id(ROUTE_DATA_ELEMENT_ID)
+                    .and_then(|script| script.text_content());
+                context.with_data(data)
+            });
+        let marker = first_comment(&root);
+
+        match (context, marker) {
+            (Some(context), Some(marker)) => {
+                #[cfg(debug_assertions)]
+                {
+                    let expected = std::any::type_name::<SsgRoot<C>>();
+                    if let Some(mismatch) = root_marker_mismatch(&marker, expected) {
+                        console::error_1(&format!("yew-ssg hydration: {}", mismatch).into());
+                    }
+                    install_panic_hook();
+                }
+                #[cfg(not(debug_assertions))]
+                let _ = marker;
+
+                let app =
+                    Renderer::<SsgRoot<C>>::with_root_and_props(root, SsgRootProps { context })
+                        .hydrate();
+                // Yew hydrates in a task queued before this one
+                #[cfg(debug_assertions)]
+                yew::platform::spawn_local(async {
+                    HYDRATING.with(|hydrating| hydrating.set(false));
+                });
+                app
+            }
+            (context, marker) => {
+                if cfg!(debug_assertions) {
+                    let reason = match (&context, marker) {
+                        (Some(_), _) => "the page contains no prerendered markup".to_string(),
+                        (None, Some(_)) => {
+                            format!("the page has no #{} script", CONTEXT_ELEMENT_ID)
+                        }
+                        (None, None) => {
+                            "the page was not generated with `hydration` enabled".to_string()
+                        }
+                    };
+                    console::warn_1(
+                        &format!("yew-ssg hydration: {}, rendering from scratch", reason).into(),
+                    );
+                }
+                let context = context.unwrap_or_else(|| {
+                    SsgRenderContext::new(
+                        web_sys::window()
+                            .and_then(|window| window.location().pathname().ok())
+                            .unwrap_or_else(|| "/".to_string()),
+                    )
+                });
+                Renderer::<SsgRoot<C>>::with_root_and_props(root, SsgRootProps { context }).render()
+            }
+        }
+    }
+
+    /// Text of the first comment in `root`, skipping leading whitespace
+    fn first_comment(root: &Element) -> Option<String> {
+        let children = root.child_nodes();
+        (0..children.length())
+            .filter_map(|index| children.item(index))
+            .find(|node| node.node_type() != Node::TEXT_NODE)
+            .filter(|node| node.node_type() == Node::COMMENT_NODE)
+            .and_then(|node| node.text_content())
+    }
+
+    /// Print panics with a hint about hydration mismatches until the tree is hydrated
+    #[cfg(debug_assertions)]
+    fn install_panic_hook() {
+        HYDRATING.with(|hydrating| hydrating.set(true));
+        yew::set_custom_panic_hook(Box::new(|info| {
+            let mut message = info.to_string();
+            if HYDRATING.with(Cell::get) {
+                message.push_str("\n\n");
+                message.push_str(MISMATCH_HINT);
+            }
+            console::error_1(&message.into());
+        }));
+    }
+}
+
+#[cfg(feature = "hydration")]
+pub use client::hydrate;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_marker_mismatch() {
+        let expected = "yew_ssg_router::hydration::SsgRoot<app::App>";
+
+        assert_eq!(root_marker_mismatch("<[]>", expected), None);
+        assert_eq!(
+            root_marker_mismatch("<[yew_ssg_router::hydration::SsgRoot<app::App>]>", expected),
+            None
+        );
+        assert!(
+            root_marker_mismatch("<[app::App]>", expected)
+                .unwrap()
+                .contains("rendered from `app::App`")
+        );
+        assert!(
+            root_marker_mismatch(" Default content container ", expected)
+                .unwrap()
+                .contains("`hydration` enabled")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_context_script_roundtrip() {
+        let context = SsgRenderContext::new("/about")
+            .with_lang(Some("de".to_string()))
+            .with_metadata(std::collections::HashMap::from([(
+                "description".to_string(),
+                "</script><b>".to_string(),
+            )]));
+
+        let script = context_script(&context);
+        assert!(script.starts_with(r#"<script type="application/json" id="yew-ssg-context">"#));
+        assert_eq!(script.matches("</script>").count(), 1);
+
+        let json = script
+            .trim_start_matches(|c| c != '>')
+            .trim_start_matches('>')
+            .trim_end_matches("</script>");
+        assert_eq!(parse_context(json), Some(context.clone()));
+
+        // Route data is embedded by its own script and not serialized with the context
+        let context = context.with_data(Some(r#"{"name":"</script>"}"#.to_string()));
+        assert!(!context_script(&context).contains("name"));
+        assert_eq!(
+            route_data_script(context.data.as_deref().unwrap()),
+            r#"<script type="application/json" id="yew-ssg-route-data">{"name":"\u003c/script>"}</script>"#
+        );
+    }
+}
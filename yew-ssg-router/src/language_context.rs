@@ -1,8 +1,9 @@
+use crate::render_context::use_ssg_render_context;
 use std::cell::RefCell;
 use yew::prelude::*;
 
 thread_local! {
-    static CURRENT_LANGUAGE: RefCell<Option<String>> = RefCell::new(None);
+    static CURRENT_LANGUAGE: RefCell<Option<String>> = const { RefCell::new(None) };
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -70,21 +71,9 @@ impl LanguageContext {
 
     /// Get the current language from various sources in priority order:
     /// 1. Thread-local storage
-    /// 2. Environment variable YEW_SSG_CURRENT_LANG
-    /// 3. Default fallback
+    /// 2. Default fallback
     pub fn get_current_lang() -> String {
-        // First try thread-local
-        if let Some(lang) = Self::get_thread_local_lang() {
-            return lang;
-        }
-
-        // Then try environment variable
-        if let Ok(lang) = std::env::var("YEW_SSG_CURRENT_LANG") {
-            return lang;
-        }
-
-        // Finally use default
-        "en".to_string()
+        Self::get_thread_local_lang().unwrap_or_else(|| "en".to_string())
     }
 }
 
@@ -115,12 +104,20 @@ pub fn language_provider(props: &LanguageProviderProps) -> Html {
 /// Hook to access the current language context
 #[hook]
 pub fn use_language() -> LanguageContext {
+    let language = use_context::<LanguageContext>();
+    let render_context = use_ssg_render_context();
+
     // First try to get from Yew context
-    if let Some(context) = use_context::<LanguageContext>() {
+    if let Some(context) = language {
         return context;
     }
 
-    // Fallback to thread-local or environment variable
+    // Then the language of the page being statically rendered
+    if let Some(lang) = render_context.and_then(|context| context.lang) {
+        return LanguageContext::new(lang);
+    }
+
+    // Fallback to thread-local
     let lang = LanguageContext::get_current_lang();
     LanguageContext::new(lang)
 }
@@ -60,13 +60,12 @@ impl LanguageNegotiator {
             let part = part.trim();
 
             // Parse language and quality factor
-            if let Some((lang, q_part)) = part.split_once(';') {
-                if let Some(q_str) = q_part.trim().strip_prefix("q=") {
-                    if let Ok(quality) = q_str.parse::<f32>() {
-                        result.push((lang.trim().to_lowercase(), quality));
-                        continue;
-                    }
-                }
+            if let Some((lang, q_part)) = part.split_once(';')
+                && let Some(q_str) = q_part.trim().strip_prefix("q=")
+                && let Ok(quality) = q_str.parse::<f32>()
+            {
+                result.push((lang.trim().to_lowercase(), quality));
+                continue;
             }
 
             // Default quality is 1.0 if not specified
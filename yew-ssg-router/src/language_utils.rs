@@ -12,13 +12,11 @@ impl LanguageUtils {
     /// Set language for current generation context
     pub fn set_generation_language(lang: &str) {
         LanguageContext::set_thread_local_lang(lang);
-        unsafe { std::env::set_var("YEW_SSG_CURRENT_LANG", lang) };
     }
 
     /// Clear language from current generation context
     pub fn clear_generation_language() {
         LanguageContext::clear_thread_local_lang();
-        unsafe { std::env::remove_var("YEW_SSG_CURRENT_LANG") };
     }
 
     /// Create a scoped language context that automatically cleans up
@@ -28,7 +26,6 @@ impl LanguageUtils {
     {
         // Save current state
         let old_thread_local = LanguageContext::get_thread_local_lang();
-        let old_env = std::env::var("YEW_SSG_CURRENT_LANG").ok();
 
         // Set new language
         Self::set_generation_language(lang);
@@ -42,11 +39,6 @@ impl LanguageUtils {
             None => LanguageContext::clear_thread_local_lang(),
         }
 
-        match old_env {
-            Some(old_lang) => unsafe { std::env::set_var("YEW_SSG_CURRENT_LANG", old_lang) },
-            None => unsafe { std::env::remove_var("YEW_SSG_CURRENT_LANG") },
-        }
-
         result
     }
 }
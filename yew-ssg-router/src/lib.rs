@@ -2,6 +2,8 @@ pub use yew_router::*;
 pub use yew_router_macro::Routable;
 
 pub mod hooks;
+mod hydration;
+mod render_context;
 mod static_link;
 mod static_navigator;
 mod static_router;
@@ -17,11 +19,27 @@ mod localized_routable;
 mod localized_route_iter;
 mod localized_route_macro;
 mod localized_switch;
+mod output_layout;
 mod route_language;
 
 pub use hooks::*;
+#[cfg(feature = "hydration")]
+pub use hydration::hydrate;
+pub use hydration::{
+    CONTEXT_ELEMENT_ID, ROUTE_DATA_ELEMENT_ID, SsgRoot, SsgRootProps, route_data_script,
+};
+#[cfg(feature = "serde")]
+pub use hydration::{context_script, parse_context};
+#[cfg(feature = "serde")]
+pub use render_context::use_route_data;
+pub use render_context::{
+    SsgRenderContext, SsgRenderContextProvider, SsgRenderContextProviderProps, use_route_params,
+    use_ssg_render_context, use_static_path,
+};
 pub use static_link::StaticLink;
 pub use static_navigator::{StaticNavigator, StaticNavigatorProvider, use_navigator};
+#[allow(deprecated)]
+pub use static_router::SsgPathContext;
 pub use static_router::StaticRouter;
 pub use static_switch::StaticSwitch;
 
@@ -33,11 +51,13 @@ pub use localized_link::{LocalizedLink, LocalizedLinkProps};
 pub use localized_routable::LocalizedRoutable;
 pub use localized_route_iter::LocalizedRouteIter;
 pub use localized_switch::{LocalizedSwitch, LocalizedSwitchProps};
+pub use output_layout::{FileNaming, OutputLayout, TrailingSlash};
 pub use route_language::{RouteLanguageExtractor, use_route_language};
 
 pub use yew_ssg_router_macros::LocalizedRoutable;
 
 pub mod prelude {
+    #[allow(deprecated)]
     pub use crate::{get_static_path, is_ssg_mode};
 
     // Import necessary types from yew_router without the components we want to replace
@@ -51,6 +71,11 @@ pub mod prelude {
     pub use crate::localized_app::{LocalizedApp, LocalizedAppProps};
     pub use crate::localized_routable::LocalizedRoutable;
     pub use crate::localized_route_iter::LocalizedRouteIter;
+    #[cfg(feature = "serde")]
+    pub use crate::render_context::use_route_data;
+    pub use crate::render_context::{
+        SsgRenderContext, use_route_params, use_ssg_render_context, use_static_path,
+    };
     pub use crate::route_language::{RouteLanguageExtractor, use_route_language};
     pub use crate::with_language;
     pub use yew_router::prelude::{Location, LocationHandle, Routable, use_location, use_route};
@@ -79,13 +104,18 @@ pub mod prelude {
 }
 
 /// Get the current path being rendered during static generation
+///
+/// The generator no longer publishes the path through the `YEW_SSG_CURRENT_PATH` environment
+/// variable, so this only returns a path that was set there by hand.
+#[deprecated(note = "use the `use_static_path` hook, which reads the page's render context")]
 pub fn get_static_path() -> Option<String> {
     std::env::var("YEW_SSG_CURRENT_PATH").ok()
 }
 
 /// Check if the application is running in static generation mode
 pub fn is_ssg_mode() -> bool {
-    if cfg!(feature = "ssg") { true } else { false }
+    cfg!(feature = "ssg")
 }
 
+#[cfg(test)]
 mod tests;
@@ -2,6 +2,7 @@ use crate::language_context::{LanguageContext, LanguageProvider};
 use crate::localized_routable::LocalizedRoutable;
 use std::fmt::Debug;
 use yew::prelude::*;
+#[cfg(not(feature = "ssg"))]
 use yew_router::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -19,15 +20,22 @@ where
 {
     #[cfg(feature = "ssg")]
     {
+        use crate::render_context::use_ssg_render_context;
         use crate::static_router::StaticRouter;
 
-        // Get the path from environment or thread-local
-        let current_path = crate::get_static_path().unwrap_or_else(|| "/".to_string());
+        // Get the path and language hint from the render context
+        let context = use_ssg_render_context().unwrap_or_default();
+        let current_path = if context.path.is_empty() {
+            "/".to_string()
+        } else {
+            context.path
+        };
 
-        // Extract language from path or use thread-local/env fallback
+        // Extract language from path or use the context/thread-local fallback
         let lang = R::recognize(&current_path)
             .and_then(|route| route.get_lang())
-            .unwrap_or_else(|| LanguageContext::get_current_lang());
+            .or(context.lang)
+            .unwrap_or_else(LanguageContext::get_current_lang);
 
         html! {
             <LanguageProvider {lang}>
@@ -50,12 +58,14 @@ where
     }
 }
 
+#[cfg(not(feature = "ssg"))]
 #[derive(Properties, PartialEq)]
 struct LocalizedRouteProviderProps {
     #[prop_or_default]
     children: Children,
 }
 
+#[cfg(not(feature = "ssg"))]
 #[function_component(LocalizedRouteProvider)]
 fn localized_route_provider<R>(props: &LocalizedRouteProviderProps) -> Html
 where
@@ -68,7 +78,7 @@ where
     // Extract language from path or use current language
     let lang = R::recognize(current_path)
         .and_then(|route| route.get_lang())
-        .unwrap_or_else(|| LanguageContext::get_current_lang());
+        .unwrap_or_else(LanguageContext::get_current_lang);
 
     html! {
         <LanguageProvider {lang}>
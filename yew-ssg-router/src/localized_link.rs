@@ -36,6 +36,10 @@ where
     R: LocalizedRoutable + 'static,
 {
     let path = props.to.to_path();
+    let path = match crate::render_context::use_ssg_render_context() {
+        Some(context) => context.href(&path),
+        None => path,
+    };
 
     // Add query string if provided
     let href = match &props.query {
@@ -55,12 +59,13 @@ where
 
     let onclick = {
         let on_click = props.onclick.clone();
+        #[cfg(not(feature = "ssg"))]
         let to = props.to.clone();
         let disabled = props.disabled;
 
         // Only perform client-side navigation in browser context
         #[cfg(not(feature = "ssg"))]
-        let navigator = yew_router::hooks::use_navigator().map(|nav| nav.clone());
+        let navigator = yew_router::hooks::use_navigator();
 
         Callback::from(move |e: MouseEvent| {
             if !disabled {
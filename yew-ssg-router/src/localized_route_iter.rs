@@ -58,10 +58,10 @@ where
             }
 
             // If we have language iterator, try to get the next language
-            if let Some(ref mut lang_iter) = self.language_iter {
-                if let Some(lang) = lang_iter.next() {
-                    return Some(L::from_route(route.clone(), Some(lang)));
-                }
+            if let Some(ref mut lang_iter) = self.language_iter
+                && let Some(lang) = lang_iter.next()
+            {
+                return Some(L::from_route(route.clone(), Some(lang)));
             }
 
             // If we've exhausted the languages, move to the next route
@@ -131,20 +131,20 @@ where
     R::Iterator: DoubleEndedIterator,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if let Some(ref mut iter) = self.route_iter {
-            if let Some(route) = iter.next_back() {
-                // First return the localized versions of this route (in reverse)
-                let mut lang_variants = Vec::new();
-                for &lang in self.languages.iter().rev() {
-                    lang_variants.push(L::from_route(route.clone(), Some(lang)));
-                }
+        if let Some(ref mut iter) = self.route_iter
+            && let Some(route) = iter.next_back()
+        {
+            // First return the localized versions of this route (in reverse)
+            let mut lang_variants = Vec::new();
+            for &lang in self.languages.iter().rev() {
+                lang_variants.push(L::from_route(route.clone(), Some(lang)));
+            }
 
-                // Then add the default version
-                lang_variants.push(L::from_route(route, None));
+            // Then add the default version
+            lang_variants.push(L::from_route(route, None));
 
-                // Return the items one by one when next_back is called
-                return lang_variants.pop();
-            }
+            // Return the items one by one when next_back is called
+            return lang_variants.pop();
         }
         None
     }
@@ -19,9 +19,9 @@ pub fn localized_switch<R>(props: &LocalizedSwitchProps<R>) -> Html
 where
     R: LocalizedRoutable + 'static,
 {
-    // For SSG, get the path from environment
+    // For SSG, get the path from the render context
     #[cfg(feature = "ssg")]
-    let current_path = { crate::get_static_path().unwrap_or_else(|| "/".to_string()) };
+    let current_path = crate::render_context::use_static_path().unwrap_or_else(|| "/".to_string());
 
     // For client-side rendering, use the location
     #[cfg(not(feature = "ssg"))]
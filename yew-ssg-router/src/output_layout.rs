@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+
+/// File naming of generated pages
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FileNaming {
+    /// `/about` is written to `about/index.html`
+    #[default]
+    DirectoryIndex,
+    /// `/about` is written to `about.html`
+    Flat,
+    /// `/about` is written to both `about/index.html` and `about.html`
+    Both,
+}
+
+/// Trailing slash policy for links and canonical URLs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TrailingSlash {
+    /// Use route paths as they are
+    #[default]
+    Preserve,
+    /// Always end paths with `/` (`/about/`)
+    Always,
+    /// Never end paths with `/`, except for the root (`/about`)
+    Never,
+}
+
+/// How generated pages are laid out on disk and addressed by links.
+///
+/// The static site generator uses the layout to name output files and canonical URLs,
+/// and passes it to components through the render context so `StaticLink` and
+/// `LocalizedLink` produce matching hrefs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OutputLayout {
+    /// How output files are named
+    pub file_naming: FileNaming,
+    /// How trailing slashes are handled in hrefs
+    pub trailing_slash: TrailingSlash,
+}
+
+impl OutputLayout {
+    /// Create a layout
+    pub fn new(file_naming: FileNaming, trailing_slash: TrailingSlash) -> Self {
+        Self {
+            file_naming,
+            trailing_slash,
+        }
+    }
+
+    /// Output files of a route relative to the output directory, primary file first
+    pub fn output_files(&self, route_path: &str) -> Vec<PathBuf> {
+        let trimmed = route_path.trim_matches('/');
+        if trimmed.is_empty() {
+            return vec![PathBuf::from("index.html")];
+        }
+
+        let directory_index = PathBuf::from(trimmed).join("index.html");
+        let flat = PathBuf::from(format!("{}.html", trimmed));
+        match self.file_naming {
+            FileNaming::DirectoryIndex => vec![directory_index],
+            FileNaming::Flat => vec![flat],
+            FileNaming::Both => vec![directory_index, flat],
+        }
+    }
+
+    /// Apply the trailing slash policy to a path, keeping any query or fragment
+    pub fn href(&self, path: &str) -> String {
+        let split = path.find(['?', '#']).unwrap_or(path.len());
+        let (path_part, suffix) = path.split_at(split);
+        if path_part.is_empty() || path_part == "/" {
+            return path.to_string();
+        }
+
+        let path_part = match self.trailing_slash {
+            TrailingSlash::Preserve => path_part.to_string(),
+            TrailingSlash::Never => path_part.trim_end_matches('/').to_string(),
+            TrailingSlash::Always => {
+                let last_segment = path_part.rsplit('/').next().unwrap_or_default();
+                if path_part.ends_with('/') || last_segment.contains('.') {
+                    path_part.to_string()
+                } else {
+                    format!("{}/", path_part)
+                }
+            }
+        };
+        format!("{}{}", path_part, suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_files() {
+        let directory = OutputLayout::default();
+        assert_eq!(
+            directory.output_files("/"),
+            vec![PathBuf::from("index.html")]
+        );
+        assert_eq!(
+            directory.output_files("/blog/post"),
+            vec![PathBuf::from("blog/post/index.html")]
+        );
+
+        let flat = OutputLayout::new(FileNaming::Flat, TrailingSlash::Never);
+        assert_eq!(flat.output_files("/"), vec![PathBuf::from("index.html")]);
+        assert_eq!(
+            flat.output_files("/about/"),
+            vec![PathBuf::from("about.html")]
+        );
+
+        let both = OutputLayout::new(FileNaming::Both, TrailingSlash::Preserve);
+        assert_eq!(
+            both.output_files("/about"),
+            vec![
+                PathBuf::from("about/index.html"),
+                PathBuf::from("about.html")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_href() {
+        let preserve = OutputLayout::default();
+        assert_eq!(preserve.href("/about"), "/about");
+        assert_eq!(preserve.href("/about/"), "/about/");
+
+        let always = OutputLayout::new(FileNaming::DirectoryIndex, TrailingSlash::Always);
+        assert_eq!(always.href("/"), "/");
+        assert_eq!(always.href("/about"), "/about/");
+        assert_eq!(always.href("/about?lang=de#top"), "/about/?lang=de#top");
+        assert_eq!(always.href("/feed.xml"), "/feed.xml");
+
+        let never = OutputLayout::new(FileNaming::Flat, TrailingSlash::Never);
+        assert_eq!(never.href("/"), "/");
+        assert_eq!(never.href("/de/"), "/de");
+        assert_eq!(never.href("/about/#team"), "/about#team");
+    }
+}
@@ -0,0 +1,275 @@
+use crate::output_layout::OutputLayout;
+use std::collections::HashMap;
+use yew::prelude::*;
+
+/// Everything a component tree needs to know about the page being statically rendered.
+///
+/// The static site generator creates one context per rendered page and hands it to the
+/// root component through [`SsgRenderContextProvider`]. Router components and hooks read
+/// from it instead of relying on process-global state, which keeps renders independent
+/// from each other.
+///
+/// With the `serde` feature the context can be embedded in a page, so the browser hydrates
+/// the page with the same context it was rendered with (see `hydrate`).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SsgRenderContext {
+    /// Path the router should match (including any path prefix)
+    pub path: String,
+    /// Values of the dynamic route parameters (e.g. `id` for `/crate/:id`)
+    pub params: HashMap<String, String>,
+    /// Language of the page, if known before rendering
+    pub lang: Option<String>,
+    /// Path prefix the site is deployed under (e.g. `/my-repo` on GitHub Pages)
+    pub base_path: Option<String>,
+    /// Merged metadata configured for the route
+    pub metadata: HashMap<String, String>,
+    /// Output layout of the site, used to shape link hrefs
+    pub layout: OutputLayout,
+    /// JSON loaded by the route's data provider (see `use_route_data`); embedded in the page
+    /// by a separate script, so it is not part of the serialized context
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub data: Option<String>,
+}
+
+impl SsgRenderContext {
+    /// Create a context for the given path
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the route parameters
+    pub fn with_params(mut self, params: HashMap<String, String>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Set the page language
+    pub fn with_lang(mut self, lang: Option<String>) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Set the path prefix
+    pub fn with_base_path(mut self, base_path: Option<String>) -> Self {
+        self.base_path = base_path;
+        self
+    }
+
+    /// Set the route metadata
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Set the output layout
+    pub fn with_layout(mut self, layout: OutputLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the route data (JSON)
+    pub fn with_data(mut self, data: Option<String>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Apply the output layout's trailing slash policy to a link path
+    pub fn href(&self, path: &str) -> String {
+        self.layout.href(path)
+    }
+
+    /// Get a single route parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(String::as_str)
+    }
+}
+
+/// Properties for the SsgRenderContextProvider component
+#[derive(Properties, PartialEq)]
+pub struct SsgRenderContextProviderProps {
+    pub context: SsgRenderContext,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Provides the render context of the current page to child components
+#[function_component(SsgRenderContextProvider)]
+pub fn ssg_render_context_provider(props: &SsgRenderContextProviderProps) -> Html {
+    html! {
+        <ContextProvider<SsgRenderContext> context={props.context.clone()}>
+            {props.children.clone()}
+        </ContextProvider<SsgRenderContext>>
+    }
+}
+
+/// Hook to access the render context of the page being generated
+///
+/// Returns `None` outside of static generation.
+#[hook]
+pub fn use_ssg_render_context() -> Option<SsgRenderContext> {
+    use_context::<SsgRenderContext>()
+}
+
+/// Hook to get the current path being rendered during static generation
+#[hook]
+pub fn use_static_path() -> Option<String> {
+    use_ssg_render_context().map(|context| context.path)
+}
+
+/// Hook to get the route parameters of the page being rendered during static generation
+#[hook]
+pub fn use_route_params() -> HashMap<String, String> {
+    use_ssg_render_context()
+        .map(|context| context.params)
+        .unwrap_or_default()
+}
+
+/// Hook to get the data loaded for the page by its route data provider.
+///
+/// Returns `None` if no provider is registered for the route or the data does not
+/// deserialize into `T`. The data is embedded in the page, so the browser gets the same
+/// value after hydration.
+#[cfg(feature = "serde")]
+#[hook]
+pub fn use_route_data<T>() -> Option<std::rc::Rc<T>>
+where
+    T: serde::de::DeserializeOwned + 'static,
+{
+    let data = use_ssg_render_context().and_then(|context| context.data);
+    use_memo(data, |data| {
+        let data = data.as_deref()?;
+        match serde_json::from_str(data) {
+            Ok(value) => Some(std::rc::Rc::new(value)),
+            Err(error) => {
+                log::warn!(
+                    "Route data does not match {}: {}",
+                    std::any::type_name::<T>(),
+                    error
+                );
+                None
+            }
+        }
+    })
+    .as_ref()
+    .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Properties, PartialEq)]
+    struct ProbeProps {}
+
+    #[function_component(Probe)]
+    fn probe(_props: &ProbeProps) -> Html {
+        let path = use_static_path().unwrap_or_else(|| "none".to_string());
+        let params = use_route_params();
+        let id = params.get("id").cloned().unwrap_or_default();
+        html! { <span>{format!("{}|{}", path, id)}</span> }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct RootProps {
+        context: Option<SsgRenderContext>,
+    }
+
+    #[function_component(Root)]
+    fn root(props: &RootProps) -> Html {
+        match &props.context {
+            Some(context) => html! {
+                <SsgRenderContextProvider context={context.clone()}>
+                    <Probe />
+                </SsgRenderContextProvider>
+            },
+            None => html! { <Probe /> },
+        }
+    }
+
+    fn render(context: Option<SsgRenderContext>) -> String {
+        futures::executor::block_on(
+            yew::LocalServerRenderer::<Root>::with_props(RootProps { context }).render(),
+        )
+    }
+
+    #[test]
+    fn test_context_builder() {
+        let context = SsgRenderContext::new("/crate/yew-ssg")
+            .with_params(HashMap::from([("id".to_string(), "yew-ssg".to_string())]))
+            .with_lang(Some("de".to_string()))
+            .with_base_path(Some("/prefix".to_string()));
+
+        assert_eq!(context.path, "/crate/yew-ssg");
+        assert_eq!(context.param("id"), Some("yew-ssg"));
+        assert_eq!(context.param("missing"), None);
+        assert_eq!(context.lang.as_deref(), Some("de"));
+        assert_eq!(context.base_path.as_deref(), Some("/prefix"));
+        assert_eq!(context.href("/about/"), "/about/");
+
+        let context = context.with_layout(OutputLayout::new(
+            crate::FileNaming::Flat,
+            crate::TrailingSlash::Never,
+        ));
+        assert_eq!(context.href("/about/"), "/about");
+    }
+
+    #[test]
+    fn test_hooks_read_provided_context() {
+        let context = SsgRenderContext::new("/crate/yew-ssg")
+            .with_params(HashMap::from([("id".to_string(), "yew-ssg".to_string())]));
+
+        let html = render(Some(context));
+        assert!(html.contains("/crate/yew-ssg|yew-ssg"));
+    }
+
+    #[test]
+    fn test_hooks_without_context() {
+        let html = render(None);
+        assert!(html.contains("none|"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_use_route_data() {
+        #[derive(serde::Deserialize)]
+        struct Crate {
+            name: String,
+        }
+
+        #[function_component(DataProbe)]
+        fn data_probe() -> Html {
+            match use_route_data::<Crate>() {
+                Some(data) => html! { <span>{&data.name}</span> },
+                None => html! { <span>{"no data"}</span> },
+            }
+        }
+
+        #[function_component(DataRoot)]
+        fn data_root(props: &RootProps) -> Html {
+            html! {
+                <SsgRenderContextProvider context={props.context.clone().unwrap_or_default()}>
+                    <DataProbe />
+                </SsgRenderContextProvider>
+            }
+        }
+
+        let render = |data: Option<&str>| {
+            let context = SsgRenderContext::new("/crate/yew-ssg").with_data(data.map(String::from));
+            futures::executor::block_on(
+                yew::LocalServerRenderer::<DataRoot>::with_props(RootProps {
+                    context: Some(context),
+                })
+                .render(),
+            )
+        };
+
+        assert!(render(Some(r#"{"name": "yew-ssg"}"#)).contains("<span>yew-ssg</span>"));
+        assert!(render(Some(r#"{"stars": 12}"#)).contains("no data"));
+        assert!(render(None).contains("no data"));
+    }
+}
@@ -14,9 +14,9 @@ pub struct RouteLanguageExtractorProps {
 pub fn route_language_extractor<R: LocalizedRoutable + 'static>(
     props: &RouteLanguageExtractorProps,
 ) -> Html {
-    // For SSG, get the path from environment
+    // For SSG, get the path from the render context
     #[cfg(feature = "ssg")]
-    let current_path = { crate::get_static_path().unwrap_or_else(|| "/".to_string()) };
+    let current_path = crate::render_context::use_static_path().unwrap_or_else(|| "/".to_string());
 
     // For client-side rendering, use the location
     #[cfg(not(feature = "ssg"))]
@@ -40,9 +40,9 @@ pub fn route_language_extractor<R: LocalizedRoutable + 'static>(
 /// Hook to extract the current route's language
 #[hook]
 pub fn use_route_language<R: LocalizedRoutable + 'static>() -> String {
-    // For SSG, get the path from environment
+    // For SSG, get the path from the render context
     #[cfg(feature = "ssg")]
-    let current_path = { crate::get_static_path().unwrap_or_else(|| "/".to_string()) };
+    let current_path = crate::render_context::use_static_path().unwrap_or_else(|| "/".to_string());
 
     // For client-side rendering, use the location
     #[cfg(not(feature = "ssg"))]
@@ -1,3 +1,4 @@
+use crate::render_context::use_ssg_render_context;
 use crate::utils::combine_with_base_url;
 use std::fmt::Debug;
 use yew::prelude::*;
@@ -37,6 +38,10 @@ where
     R: Routable + PartialEq + Clone + Debug + 'static,
 {
     let path = props.to.to_path();
+    let path = match use_ssg_render_context() {
+        Some(context) => context.href(&path),
+        None => path,
+    };
     let href = combine_with_base_url(&path);
 
     // In SSG mode, render a regular <a> tag
@@ -1,14 +1,20 @@
+use crate::output_layout::OutputLayout;
+use crate::render_context::use_ssg_render_context;
 use crate::utils::get_base_url;
 use std::fmt::Debug;
 use yew::prelude::*;
 use yew_router::Routable;
 
 /// A navigator implementation for static rendering (SSR/SSG)
+///
+/// Navigation only logs during static rendering. In a hydrated page (feature `hydration`)
+/// it loads the target page, as every route is a separate prerendered file.
 #[derive(Debug, Clone, PartialEq)]
 pub struct StaticNavigator {
     path: String,
     base_url: String,
     basename: Option<String>,
+    layout: OutputLayout,
 }
 
 /// Properties for the StaticNavigator
@@ -22,13 +28,11 @@ pub struct StaticNavigatorProps {
 
 impl StaticNavigator {
     pub fn new() -> Self {
-        let path = crate::get_static_path().unwrap_or_else(|| "/".to_string());
-        let base_url = get_base_url();
-
         Self {
-            path,
-            base_url,
+            path: "/".to_string(),
+            base_url: get_base_url(),
             basename: None,
+            layout: OutputLayout::default(),
         }
     }
 
@@ -39,15 +43,28 @@ impl StaticNavigator {
             path: path.to_string(),
             base_url: base_url.to_string(),
             basename: None,
+            layout: OutputLayout::default(),
         }
     }
 
+    /// Set the path the navigator reports as current
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
     // Add a new method that accepts basename
     pub fn with_basename(mut self, basename: Option<String>) -> Self {
         self.basename = basename;
         self
     }
 
+    /// Set the output layout used to shape the hrefs of navigation targets
+    pub fn with_layout(mut self, layout: OutputLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
     pub fn path(&self) -> &str {
         &self.path
     }
@@ -60,40 +77,70 @@ impl StaticNavigator {
         self.basename.as_deref()
     }
 
-    /// Logs push navigation attempt during static rendering
+    /// Href of a route, shaped like the hrefs of `StaticLink`
+    pub fn href<R: Routable>(&self, route: &R) -> String {
+        let path = self.layout.href(&route.to_path());
+        if self.base_url.is_empty() {
+            path
+        } else {
+            format!(
+                "{}/{}",
+                self.base_url.trim_end_matches('/'),
+                path.trim_start_matches('/')
+            )
+        }
+    }
+
+    /// Loads the page of a route, or logs the attempt during static rendering
     pub fn push<R>(&self, route: &R)
     where
         R: Routable + Debug,
     {
+        #[cfg(all(feature = "hydration", target_arch = "wasm32"))]
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().assign(&self.href(route));
+            return;
+        }
         log::debug!(
             "StaticNavigator: Push route {:?} attempted during static render",
             route
         );
     }
 
-    /// Logs replace navigation attempt during static rendering
+    /// Replaces the current page with the page of a route, or logs the attempt during
+    /// static rendering
     pub fn replace<R>(&self, route: &R)
     where
         R: Routable + Debug,
     {
+        #[cfg(all(feature = "hydration", target_arch = "wasm32"))]
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().replace(&self.href(route));
+            return;
+        }
         log::debug!(
             "StaticNavigator: Replace route {:?} attempted during static render",
             route
         );
     }
 
-    /// Logs back navigation attempt during static rendering
+    /// Goes back in the browser history, or logs the attempt during static rendering
     pub fn back(&self) {
-        log::debug!("StaticNavigator: Back navigation attempted during static render");
+        self.go(-1);
     }
 
-    /// Logs forward navigation attempt during static rendering
+    /// Goes forward in the browser history, or logs the attempt during static rendering
     pub fn forward(&self) {
-        log::debug!("StaticNavigator: Forward navigation attempted during static render");
+        self.go(1);
     }
 
-    /// Logs go navigation attempt during static rendering
+    /// Moves through the browser history, or logs the attempt during static rendering
     pub fn go(&self, delta: isize) {
+        #[cfg(all(feature = "hydration", target_arch = "wasm32"))]
+        if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+            let _ = history.go_with_delta(delta as i32);
+            return;
+        }
         log::debug!(
             "StaticNavigator: Go({}) navigation attempted during static render",
             delta
@@ -101,6 +148,12 @@ impl StaticNavigator {
     }
 }
 
+impl Default for StaticNavigator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Hook to access the StaticNavigator within components
 #[hook]
 pub fn use_navigator() -> Option<StaticNavigator> {
@@ -111,7 +164,15 @@ pub fn use_navigator() -> Option<StaticNavigator> {
 /// Component that provides StaticNavigator context
 #[function_component(StaticNavigatorProvider)]
 pub fn static_navigator_provider(props: &StaticNavigatorProps) -> Html {
-    let navigator = StaticNavigator::new().with_basename(props.basename.clone());
+    let context = use_ssg_render_context();
+    let path = context
+        .as_ref()
+        .map(|context| context.path.clone())
+        .unwrap_or_else(|| "/".to_string());
+    let navigator = StaticNavigator::new()
+        .with_path(path)
+        .with_basename(props.basename.clone())
+        .with_layout(context.map(|context| context.layout).unwrap_or_default());
 
     html! {
         <ContextProvider<StaticNavigator> context={navigator}>
@@ -157,6 +218,18 @@ mod tests {
         navigator.go(1);
     }
 
+    #[test]
+    #[serial]
+    fn test_navigator_href() {
+        let navigator = StaticNavigator::new_with_path_and_base("/", "");
+        assert_eq!(navigator.href(&TestRoute::About), "/about");
+
+        let navigator = StaticNavigator::new_with_path_and_base("/", "/base/").with_layout(
+            OutputLayout::new(crate::FileNaming::Flat, crate::TrailingSlash::Always),
+        );
+        assert_eq!(navigator.href(&TestRoute::About), "/base/about/");
+    }
+
     // Test that simply creates the component without trying to render it
     #[test]
     #[serial]
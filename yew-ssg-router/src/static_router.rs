@@ -1,3 +1,4 @@
+use crate::render_context::SsgRenderContext;
 use crate::static_navigator::StaticNavigatorProvider;
 use yew::prelude::*;
 
@@ -11,13 +12,26 @@ pub struct StaticRouterProps {
 }
 
 /// A simplified context provider that holds the current SSG path
+#[deprecated(
+    note = "use `SsgRenderContext`, read with `use_ssg_render_context` or `use_static_path`"
+)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct SsgPathContext {
     pub path: String,
     pub basename: Option<String>,
 }
 
-/// A very simple router that just provides the current path from the SSG environment
+#[allow(deprecated)]
+impl From<&SsgRenderContext> for SsgPathContext {
+    fn from(context: &SsgRenderContext) -> Self {
+        Self {
+            path: context.path.clone(),
+            basename: context.base_path.clone(),
+        }
+    }
+}
+
+/// A very simple router that just provides the current path from the SSG render context
 #[function_component(StaticRouter)]
 pub fn static_router(props: &StaticRouterProps) -> Html {
     // Wrap with StaticNavigatorProvider
@@ -1,3 +1,4 @@
+use crate::render_context::use_static_path;
 use log::{info, warn};
 use yew::prelude::*;
 use yew_router::Routable;
@@ -17,8 +18,8 @@ pub fn static_switch<R>(props: &StaticSwitchProps<R>) -> Html
 where
     R: Routable + Clone + PartialEq + std::fmt::Debug + 'static,
 {
-    // Get the current path directly from the environment variable during SSG
-    let current_path = crate::get_static_path().unwrap_or_else(|| "/".to_string());
+    // Get the current path from the render context provided by the generator
+    let current_path = use_static_path().unwrap_or_else(|| "/".to_string());
 
     info!("StaticSwitch: Current path = {}", current_path);
 
@@ -27,26 +27,25 @@ fn test_thread_local_language_cycle() {
 }
 
 //
-// 2. Environment fallback when thread‑local is absent
+// 2. The environment is no longer consulted
 //
 #[test]
 #[serial]
-fn test_env_fallback_when_no_thread_local() {
+fn test_env_is_ignored_without_thread_local() {
     // Ensure clean thread-local
     LanguageContext::clear_thread_local_lang();
 
     unsafe { env::set_var("YEW_SSG_CURRENT_LANG", "es") };
-    assert_eq!(LanguageContext::get_current_lang(), "es");
+    assert_eq!(LanguageContext::get_current_lang(), "en");
 
     // Thread-local still wins if set
     LanguageContext::set_thread_local_lang("de");
     assert_eq!(LanguageContext::get_current_lang(), "de");
 
-    // After clearing TL, env fallback returns
+    // After clearing TL, the default fallback returns
     LanguageContext::clear_thread_local_lang();
-    assert_eq!(LanguageContext::get_current_lang(), "es");
+    assert_eq!(LanguageContext::get_current_lang(), "en");
 
     // Cleanup
     unsafe { env::remove_var("YEW_SSG_CURRENT_LANG") };
-    assert_eq!(LanguageContext::get_current_lang(), "en");
 }
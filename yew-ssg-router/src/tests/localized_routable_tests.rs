@@ -25,6 +25,7 @@ impl Debug for MockRoute {
 }
 
 impl Routable for MockRoute {
+    #[allow(clippy::manual_map)]
     fn from_path(path: &str, params: &HashMap<&str, &str>) -> Option<Self> {
         match path {
             "/" => Some(Self::Home),
@@ -0,0 +1,237 @@
+//! Static asset pipeline.
+//!
+//! An [`AssetPipeline`] copies a directory of static files (stylesheets, images, fonts, ...)
+//! into the output directory. With fingerprinting enabled every file is renamed with a hash
+//! of its content (`style.css` becomes `style.3f9a1c.css`), so it can be cached forever.
+//! The [`AssetManifest`] maps source paths to output paths; it is written as
+//! `asset-manifest.json`, used by the
+//! [`AssetProcessor`](crate::processors::AssetProcessor) to rewrite references in the
+//! generated pages and by the `asset_url` template function.
+
+use crate::error::{IoContext, SsgError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the asset manifest, relative to the output directory
+pub const ASSET_MANIFEST_FILE_NAME: &str = "asset-manifest.json";
+
+/// Number of hex digits of the content hash in fingerprinted file names
+const FINGERPRINT_LENGTH: usize = 6;
+
+/// Static files copied into the output directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetPipeline {
+    /// Directory whose files are copied (e.g. `public`)
+    pub source_dir: PathBuf,
+    /// Rename files with a hash of their content (`style.3f9a1c.css`)
+    #[serde(default)]
+    pub fingerprint: bool,
+    /// Files (relative to `source_dir`) that keep their name, e.g. `favicon.ico`;
+    /// HTML files always keep their name
+    #[serde(default)]
+    pub keep_names: Vec<String>,
+}
+
+impl AssetPipeline {
+    /// Copy the files of `source_dir` without renaming them
+    pub fn new<P: Into<PathBuf>>(source_dir: P) -> Self {
+        Self {
+            source_dir: source_dir.into(),
+            fingerprint: false,
+            keep_names: Vec::new(),
+        }
+    }
+
+    /// Rename files with a hash of their content
+    pub fn fingerprint(mut self, fingerprint: bool) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    /// Keep the name of a file when fingerprinting
+    pub fn keep_name(mut self, path: &str) -> Self {
+        self.keep_names
+            .push(path.trim_start_matches('/').to_string());
+        self
+    }
+}
+
+/// Output path of every asset by source path, both relative with `/` separators
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetManifest {
+    /// Source paths mapped to output paths (e.g. `css/style.css` → `css/style.3f9a1c.css`)
+    pub files: BTreeMap<String, String>,
+}
+
+impl AssetManifest {
+    /// Read and hash the files of a pipeline, without copying anything
+    pub fn scan(pipeline: &AssetPipeline) -> Result<Self, SsgError> {
+        let mut sources = Vec::new();
+        collect_files(&pipeline.source_dir, Path::new(""), &mut sources)?;
+
+        let mut files = BTreeMap::new();
+        for source in sources {
+            let path = source.to_string_lossy().replace('\\', "/");
+            let keep_name = !pipeline.fingerprint
+                || path.ends_with(".html")
+                || pipeline.keep_names.contains(&path);
+            let output = if keep_name {
+                path.clone()
+            } else {
+                let file = pipeline.source_dir.join(&source);
+                let content = fs::read(&file).with_path(&file)?;
+                fingerprinted(&path, &content)
+            };
+            files.insert(path, output);
+        }
+        Ok(Self { files })
+    }
+
+    /// Output path of an asset, relative to the output directory
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files
+            .get(path.trim_start_matches('/'))
+            .map(String::as_str)
+    }
+
+    /// Source path of an output path, the reverse of [`Self::get`]
+    pub fn source(&self, output: &str) -> Option<&str> {
+        let output = output.trim_start_matches('/');
+        self.files
+            .iter()
+            .find(|(_, file)| file.as_str() == output)
+            .map(|(source, _)| source.as_str())
+    }
+
+    /// Rewrite a root-relative reference (`/css/style.css?v=1`) below `base_path` to the
+    /// output path of the asset; `None` if it does not name an asset
+    pub fn rewrite(&self, url: &str, base_path: &str) -> Option<String> {
+        let base_path = base_path.trim_end_matches('/');
+        let path = url.strip_prefix(base_path)?;
+        if !path.starts_with('/') {
+            return None;
+        }
+        let end = path.find(['?', '#']).unwrap_or(path.len());
+        let output = self.get(&path[..end])?;
+        Some(format!("{}/{}{}", base_path, output, &path[end..]))
+    }
+
+    /// Copy the assets of `pipeline` below `dir` and write the manifest next to them.
+    ///
+    /// Fingerprinted files listed in the previous manifest but no longer produced are
+    /// removed. Returns the written files.
+    pub fn copy(&self, pipeline: &AssetPipeline, dir: &Path) -> Result<Vec<PathBuf>, SsgError> {
+        let manifest_path = dir.join(ASSET_MANIFEST_FILE_NAME);
+        if let Ok(previous) = fs::read_to_string(&manifest_path)
+            && let Ok(previous) = serde_json::from_str::<AssetManifest>(&previous)
+        {
+            for (source, output) in &previous.files {
+                if source != output && self.source(output).is_none() {
+                    let stale = dir.join(output);
+                    if stale.is_file() {
+                        fs::remove_file(&stale).with_path(&stale)?;
+                    }
+                }
+            }
+        }
+
+        let mut written = Vec::new();
+        for (source, output) in &self.files {
+            let from = pipeline.source_dir.join(source);
+            let to = dir.join(output);
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent).with_path(parent)?;
+            }
+            fs::copy(&from, &to).with_path(&from)?;
+            written.push(to);
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(SsgError::custom)?;
+        fs::write(&manifest_path, json).with_path(&manifest_path)?;
+        written.push(manifest_path);
+        Ok(written)
+    }
+}
+
+/// `css/style.css` with the content hash inserted before the extension
+fn fingerprinted(path: &str, content: &[u8]) -> String {
+    let hash = format!("{:x}", Sha256::digest(content));
+    let hash = &hash[..FINGERPRINT_LENGTH];
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), path),
+    };
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => {
+            format!("{}{}.{}.{}", dir, stem, hash, extension)
+        }
+        _ => format!("{}{}.{}", dir, name, hash),
+    }
+}
+
+/// Collect the files below `dir`, relative to the source directory
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), SsgError> {
+    let path = root.join(dir);
+    for entry in fs::read_dir(&path).with_path(&path)? {
+        let entry = entry.with_path(&path)?;
+        let relative = dir.join(entry.file_name());
+        if entry.path().is_dir() {
+            collect_files(root, &relative, files)?;
+        } else {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_and_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let public = dir.path().join("public");
+        let dist = dir.path().join("dist");
+        fs::create_dir_all(public.join("css")).unwrap();
+        fs::write(public.join("css/style.css"), "body{}").unwrap();
+        fs::write(public.join("favicon.ico"), "icon").unwrap();
+        fs::write(public.join("google123.html"), "verification").unwrap();
+
+        let pipeline = AssetPipeline::new(&public)
+            .fingerprint(true)
+            .keep_name("favicon.ico");
+        let manifest = AssetManifest::scan(&pipeline).unwrap();
+
+        let style = manifest.get("/css/style.css").unwrap().to_string();
+        assert!(style.starts_with("css/style."));
+        assert_eq!(style.len(), "css/style..css".len() + FINGERPRINT_LENGTH);
+        assert_eq!(manifest.get("favicon.ico"), Some("favicon.ico"));
+        assert_eq!(manifest.get("google123.html"), Some("google123.html"));
+        assert_eq!(
+            manifest.rewrite("/blog/css/style.css?v=1", "/blog"),
+            Some(format!("/blog/{}?v=1", style))
+        );
+        assert_eq!(manifest.rewrite("/css/missing.css", ""), None);
+        assert_eq!(manifest.rewrite("css/style.css", ""), None);
+
+        manifest.copy(&pipeline, &dist).unwrap();
+        assert_eq!(fs::read_to_string(dist.join(&style)).unwrap(), "body{}");
+        assert!(dist.join("favicon.ico").is_file());
+
+        // A changed file gets a new name and the old one is removed
+        fs::write(public.join("css/style.css"), "body{margin:0}").unwrap();
+        let manifest = AssetManifest::scan(&pipeline).unwrap();
+        manifest.copy(&pipeline, &dist).unwrap();
+        assert_ne!(manifest.get("css/style.css").unwrap(), style);
+        assert!(!dist.join(&style).exists());
+
+        let saved: AssetManifest =
+            serde_json::from_str(&fs::read_to_string(dist.join(ASSET_MANIFEST_FILE_NAME)).unwrap())
+                .unwrap();
+        assert_eq!(saved, manifest);
+    }
+}
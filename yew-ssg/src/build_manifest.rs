@@ -0,0 +1,293 @@
+//! Manifest used by incremental builds.
+//!
+//! The manifest lives in the output directory and maps every generated page to a hash
+//! of everything that went into it: the rendered content, the merged metadata, the
+//! template and the generator/processor configuration. Pages whose hash did not change
+//! since the previous build are not written again, so their files keep their timestamps.
+//! A full build also drops the pages that are no longer part of the site.
+
+use crate::error::{IoContext, SsgError};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest inside the output directory
+pub const MANIFEST_FILE_NAME: &str = ".yew-ssg-manifest.json";
+
+/// Format version, bumped whenever the hash inputs change
+const MANIFEST_VERSION: u32 = 1;
+
+/// Hash and output file recorded for a single page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Hash of the page inputs
+    pub hash: String,
+    /// Written file, relative to the output directory
+    pub file: PathBuf,
+}
+
+/// Record of the pages written by previous builds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildManifest {
+    /// Manifest format version
+    pub version: u32,
+    /// Entries keyed by page path (including any path prefix)
+    pub pages: BTreeMap<String, ManifestEntry>,
+}
+
+impl Default for BuildManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            pages: BTreeMap::new(),
+        }
+    }
+}
+
+impl BuildManifest {
+    /// Location of the manifest for the given output directory
+    pub fn path(output_dir: &Path) -> PathBuf {
+        output_dir.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Load the manifest from the output directory.
+    ///
+    /// A missing, unreadable or outdated manifest yields an empty one, which makes the
+    /// next build write every page.
+    pub fn load(output_dir: &Path) -> Self {
+        let path = Self::path(output_dir);
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str::<Self>(&content) {
+            Ok(manifest) if manifest.version == MANIFEST_VERSION => manifest,
+            Ok(_) => {
+                warn!("Ignoring build manifest {:?} with outdated version", path);
+                Self::default()
+            }
+            Err(e) => {
+                warn!("Ignoring unreadable build manifest {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the manifest to the output directory
+    pub fn save(&self, output_dir: &Path) -> Result<(), SsgError> {
+        fs::create_dir_all(output_dir).with_path(output_dir)?;
+        let path = Self::path(output_dir);
+        fs::write(&path, serde_json::to_string_pretty(self)?).with_path(&path)?;
+        Ok(())
+    }
+
+    /// Check whether a page is unchanged and its file is still present
+    pub fn is_unchanged(&self, page: &str, hash: &str, output_dir: &Path) -> bool {
+        self.pages
+            .get(page)
+            .is_some_and(|entry| entry.hash == hash && output_dir.join(&entry.file).exists())
+    }
+
+    /// Drop the entries of pages not in `pages`, returning their output files
+    pub fn retain_pages(&mut self, pages: &HashSet<&str>) -> Vec<PathBuf> {
+        let stale: Vec<String> = self
+            .pages
+            .keys()
+            .filter(|page| !pages.contains(page.as_str()))
+            .cloned()
+            .collect();
+        stale
+            .into_iter()
+            .filter_map(|page| self.pages.remove(&page))
+            .map(|entry| entry.file)
+            .collect()
+    }
+
+    /// Record the hash and output file of a page
+    pub fn record(&mut self, page: impl Into<String>, hash: impl Into<String>, file: PathBuf) {
+        self.pages.insert(
+            page.into(),
+            ManifestEntry {
+                hash: hash.into(),
+                file,
+            },
+        );
+    }
+}
+
+/// Hash the inputs of a page.
+///
+/// Metadata is hashed in key order so the result does not depend on `HashMap` iteration.
+pub fn page_hash(
+    content: &str,
+    metadata: &HashMap<String, String>,
+    template: &str,
+    configuration: &str,
+) -> String {
+    let mut hasher = Sha256::new();
+    let mut update = |part: &str| {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    };
+
+    update(content);
+    update(template);
+    update(configuration);
+    for (key, value) in metadata.iter().collect::<BTreeMap<_, _>>() {
+        update(key);
+        update(value);
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::report::PageStatus;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+
+    fn metadata(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_page_hash_is_stable_and_sensitive() {
+        let meta = metadata(&[("title", "Home"), ("lang", "en")]);
+        let hash = page_hash("<p>Hi</p>", &meta, "{{ content }}", "config");
+
+        assert_eq!(
+            hash,
+            page_hash("<p>Hi</p>", &meta, "{{ content }}", "config")
+        );
+        assert_ne!(
+            hash,
+            page_hash("<p>Ho</p>", &meta, "{{ content }}", "config")
+        );
+        assert_ne!(hash, page_hash("<p>Hi</p>", &meta, "{{ title }}", "config"));
+        assert_ne!(
+            hash,
+            page_hash("<p>Hi</p>", &meta, "{{ content }}", "other")
+        );
+
+        let changed = metadata(&[("title", "Start"), ("lang", "en")]);
+        assert_ne!(
+            hash,
+            page_hash("<p>Hi</p>", &changed, "{{ content }}", "config")
+        );
+    }
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("index.html"), "<p>Hi</p>").unwrap();
+
+        let mut manifest = BuildManifest::default();
+        manifest.record("/", "abc", PathBuf::from("index.html"));
+        manifest.record("/about", "def", PathBuf::from("about/index.html"));
+        manifest.save(dir.path()).unwrap();
+
+        let loaded = BuildManifest::load(dir.path());
+        assert_eq!(loaded, manifest);
+        assert!(loaded.is_unchanged("/", "abc", dir.path()));
+        assert!(!loaded.is_unchanged("/", "xyz", dir.path()));
+        // The file of /about was never written
+        assert!(!loaded.is_unchanged("/about", "def", dir.path()));
+
+        let mut pruned = loaded.clone();
+        let stale = pruned.retain_pages(&HashSet::from(["/"]));
+        assert_eq!(stale, vec![PathBuf::from("about/index.html")]);
+        assert_eq!(pruned.pages.keys().collect::<Vec<_>>(), vec!["/"]);
+    }
+
+    #[test]
+    fn test_invalid_manifest_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(BuildManifest::path(dir.path()), "not json").unwrap();
+
+        assert_eq!(BuildManifest::load(dir.path()), BuildManifest::default());
+    }
+
+    #[tokio::test]
+    async fn test_incremental_build_skips_unchanged_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let build = |title: &str| {
+            let config = SsgConfigBuilder::new()
+                .output_dir(dir.path())
+                .route_metadata(
+                    "/crate/:id",
+                    HashMap::from([("title".to_string(), title.to_string())]),
+                )
+                .add_route_param("/crate/:id", "id", ["a"])
+                .incremental(true)
+                .build();
+            StaticSiteGenerator::new(config).unwrap()
+        };
+        let run = |generator: StaticSiteGenerator| async move {
+            let mut report = generator
+                .generate::<ContextRoute, ContextProbe>()
+                .await
+                .unwrap();
+            report.merge(
+                generator
+                    .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+                    .await
+                    .unwrap(),
+            );
+            report
+        };
+
+        assert_eq!(run(build("Crate")).await.written(), 3);
+        let manifest = BuildManifest::load(dir.path());
+        assert!(manifest.pages.contains_key("/"));
+        assert!(manifest.pages.contains_key("/crate/a"));
+
+        // Mark the written files so rewrites become visible
+        let home = dir.path().join("index.html");
+        let crate_page = dir.path().join("crate/a/index.html");
+        fs::write(&home, "untouched").unwrap();
+        fs::write(&crate_page, "untouched").unwrap();
+
+        assert_eq!(run(build("Crate")).await.unchanged(), 3);
+        assert_eq!(fs::read_to_string(&home).unwrap(), "untouched");
+        assert_eq!(fs::read_to_string(&crate_page).unwrap(), "untouched");
+
+        let report = run(build("Changed")).await;
+        assert_eq!(report.page("/").unwrap().status, PageStatus::Unchanged);
+        assert_eq!(report.page("/crate/a").unwrap().status, PageStatus::Written);
+        assert_eq!(fs::read_to_string(&home).unwrap(), "untouched");
+        assert!(
+            fs::read_to_string(&crate_page)
+                .unwrap()
+                .contains("<title>Changed</title>")
+        );
+
+        // A full build drops the pages that are no longer part of the site
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .add_route_param("/crate/:id", "id", ["b"])
+            .incremental(true)
+            .build();
+        let report = StaticSiteGenerator::new(config)
+            .unwrap()
+            .build::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        assert_eq!(report.removed, vec![PathBuf::from("crate/a/index.html")]);
+        assert!(!dir.path().join("crate/a").exists());
+        assert!(dir.path().join("crate/b/index.html").exists());
+        assert!(
+            !BuildManifest::load(dir.path())
+                .pages
+                .contains_key("/crate/a")
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Dry-run planning of a build.
+//!
+//! [`BuildPlan`] lists every page a build would write, without rendering anything, and
+//! the problems that would make the output incomplete or ambiguous: several routes
+//! writing the same file, routes that do not survive a `to_path` / `recognize` round
+//! trip and parameter combinations no route recognizes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A page the build would write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedPage {
+    /// Route path of the page (including any path prefix)
+    pub route: String,
+    /// Output file, relative to the output directory
+    pub output_file: PathBuf,
+    /// Additional files holding the same page (e.g. `about.html` next to `about/index.html`)
+    pub copies: Vec<PathBuf>,
+    /// Route pattern for parameterized routes
+    pub pattern: Option<String>,
+    /// Parameter values for parameterized routes
+    pub params: HashMap<String, String>,
+}
+
+/// A problem found while planning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanIssue {
+    /// Several routes would be written to the same file
+    Collision {
+        /// Output file, relative to the output directory
+        output_file: PathBuf,
+        /// Routes mapping to the file
+        routes: Vec<String>,
+    },
+    /// `R::recognize(route.to_path())` does not return the route itself
+    RoundTrip {
+        /// Path produced by `to_path`
+        path: String,
+        /// Path of the route recognized instead, if any
+        recognized: Option<String>,
+    },
+    /// A parameter combination produced a path no route recognizes
+    UnrecognizedParams {
+        /// Route pattern from the configuration
+        pattern: String,
+        /// Path constructed from the pattern
+        path: String,
+    },
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanIssue::Collision {
+                output_file,
+                routes,
+            } => write!(
+                f,
+                "routes {} all write to {:?}",
+                routes.join(", "),
+                output_file
+            ),
+            PlanIssue::RoundTrip {
+                path,
+                recognized: Some(recognized),
+            } => write!(f, "path {} is recognized as {}", path, recognized),
+            PlanIssue::RoundTrip {
+                path,
+                recognized: None,
+            } => write!(f, "path {} is not recognized by the router", path),
+            PlanIssue::UnrecognizedParams { pattern, path } => {
+                write!(f, "no route recognizes {} (pattern {})", path, pattern)
+            }
+        }
+    }
+}
+
+/// Pages and issues of a planned build
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildPlan {
+    /// Pages in build order
+    pub pages: Vec<PlannedPage>,
+    /// Problems found while planning
+    pub issues: Vec<PlanIssue>,
+}
+
+impl BuildPlan {
+    /// Returns true if no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Find the planned page of a route
+    pub fn page(&self, route: &str) -> Option<&PlannedPage> {
+        self.pages.iter().find(|page| page.route == route)
+    }
+
+    /// Iterate over the output collisions
+    pub fn collisions(&self) -> impl Iterator<Item = &PlanIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| matches!(issue, PlanIssue::Collision { .. }))
+    }
+
+    /// Record a collision issue for every output file claimed by more than one page
+    pub(crate) fn detect_collisions(&mut self) {
+        let mut by_file: BTreeMap<&PathBuf, Vec<String>> = BTreeMap::new();
+        for page in &self.pages {
+            for file in std::iter::once(&page.output_file).chain(&page.copies) {
+                by_file.entry(file).or_default().push(page.route.clone());
+            }
+        }
+
+        let collisions: Vec<_> = by_file
+            .into_iter()
+            .filter(|(_, routes)| routes.len() > 1)
+            .map(|(output_file, routes)| PlanIssue::Collision {
+                output_file: output_file.clone(),
+                routes,
+            })
+            .collect();
+        self.issues.extend(collisions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::error::SsgError;
+    use crate::failure::FailurePolicy;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+
+    fn page(route: &str, output_file: &str) -> PlannedPage {
+        PlannedPage {
+            route: route.to_string(),
+            output_file: PathBuf::from(output_file),
+            copies: Vec::new(),
+            pattern: None,
+            params: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_detect_collisions() {
+        let mut plan = BuildPlan {
+            pages: vec![
+                page("/", "index.html"),
+                page("/about", "about/index.html"),
+                page("/about/", "about/index.html"),
+            ],
+            issues: Vec::new(),
+        };
+        plan.detect_collisions();
+
+        assert!(!plan.is_ok());
+        assert_eq!(
+            plan.collisions().collect::<Vec<_>>(),
+            vec![&PlanIssue::Collision {
+                output_file: PathBuf::from("about/index.html"),
+                routes: vec!["/about".to_string(), "/about/".to_string()],
+            }]
+        );
+        assert_eq!(
+            plan.issues[0].to_string(),
+            "routes /about, /about/ all write to \"about/index.html\""
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_overwrite_protection() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .add_route_param("/crate/:id", "id", ["a"])
+            .add_route_param("/missing/:id", "id", ["x"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let plan = generator.plan::<ContextRoute>();
+        assert_eq!(plan.pages.len(), 3);
+        assert_eq!(
+            plan.page("/crate/a").unwrap().output_file,
+            PathBuf::from("crate/a/index.html")
+        );
+        assert_eq!(
+            plan.page("/crate/a").unwrap().pattern.as_deref(),
+            Some("/crate/:id")
+        );
+        assert_eq!(plan.collisions().count(), 0);
+        // The default `Crate` variant renders to `/crate/`, which does not round-trip
+        assert!(plan.issues.contains(&PlanIssue::RoundTrip {
+            path: "/crate/".to_string(),
+            recognized: None,
+        }));
+        assert!(plan.issues.contains(&PlanIssue::UnrecognizedParams {
+            pattern: "/missing/:id".to_string(),
+            path: "/missing/x".to_string(),
+        }));
+        assert!(!dir.path().join("index.html").exists());
+
+        // Unrecognized parameters fail the build under the default policy
+        let err = generator
+            .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            SsgError::RouteRecognition { path, pattern }
+                if path == "/missing/x" && pattern.as_deref() == Some("/missing/:id")
+        ));
+        assert!(!dir.path().join("crate/a/index.html").exists());
+
+        // Other policies render the rest and list the combination as failed
+        let skip_dir = tempfile::tempdir().unwrap();
+        let report = StaticSiteGenerator::new(
+            SsgConfigBuilder::new()
+                .output_dir(skip_dir.path())
+                .add_route_param("/crate/:id", "id", ["a"])
+                .add_route_param("/missing/:id", "id", ["x"])
+                .failure_policy(FailurePolicy::SkipRoute)
+                .build(),
+        )
+        .unwrap()
+        .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+        .await
+        .unwrap();
+        assert_eq!(report.pages.len(), 1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].route, "/missing/x");
+        assert_eq!(
+            report.failures[0].message,
+            "No route recognized for path /missing/x (pattern /missing/:id)"
+        );
+
+        // Every run starts over, so the same pages can be generated again
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+    }
+}
@@ -1,14 +1,28 @@
+use crate::assets::AssetPipeline;
+use crate::failure::FailurePolicy;
+use crate::feeds::FeedConfig;
 use crate::generator::Generator;
 use crate::generator_collection::GeneratorCollection;
 use crate::generators::{
-    CanonicalLinkGenerator, JsonLdGenerator, MetaTagGenerator, OpenGraphGenerator,
-    RobotsMetaGenerator, TitleGenerator, TwitterCardGenerator,
+    CanonicalLinkGenerator, FeedLinkGenerator, JsonLdGenerator, MetaTagGenerator,
+    OpenGraphGenerator, RobotsMetaGenerator, TitleGenerator, TwitterCardGenerator,
 };
+use crate::link_check::LinkCheck;
+use crate::plugin::BuildPlugin;
+use crate::precompress::Precompress;
 use crate::processor::Processor;
 use crate::processor_collection::ProcessorCollection;
-use crate::processors::{AttributeProcessor, TemplateVariableProcessor};
-use std::collections::{HashMap, HashSet};
+use crate::processors::{AttributeProcessor, MinifyProcessor, TemplateVariableProcessor};
+use crate::redirects::{Redirect, RedirectHost, Redirects};
+use crate::robots_txt::RobotsTxt;
+use crate::route_data::{RouteData, RouteDataProvider};
+use crate::template_functions::TemplateFunctions;
+use minijinja::Value;
+use minijinja::functions::Function;
+use minijinja::value::{FunctionArgs, FunctionResult};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use yew_router::OutputLayout;
 
 /// Defines parameter names and their valid values for routes with path parameters
 #[derive(Debug, Clone, Default)]
@@ -116,14 +130,56 @@ pub struct SsgConfig {
     pub output_dir: PathBuf,
     pub template_path: Option<PathBuf>,
     pub default_template: String,
+    /// Directory of named layouts and partials, selected per page by the `layout` metadata key
+    pub templates_dir: Option<PathBuf>,
+    /// Named templates registered in addition to the templates directory
+    pub templates: BTreeMap<String, String>,
+    /// Custom filters, functions and globals of the template environment
+    pub template_functions: TemplateFunctions,
     pub global_metadata: HashMap<String, String>,
     pub route_metadata: HashMap<String, HashMap<String, String>>,
     pub generators: GeneratorCollection,
     pub processors: ProcessorCollection,
+    /// Plugins notified about the build and every generated page
+    pub plugins: Vec<Box<dyn BuildPlugin>>,
     /// Parameter definitions for routes with dynamic segments
     pub route_params: HashMap<String, RouteParams>,
+    /// Data providers run before rendering the routes matching their pattern
+    pub route_data: Vec<RouteData>,
     /// Base directory for asset files (images, JSON-LD, etc.)
     pub assets_base_dir: Option<String>,
+    /// Static files copied into the output directory, optionally fingerprinted
+    pub assets: Option<AssetPipeline>,
+    /// Directories read by components while rendering (e.g. markdown sources)
+    pub content_dirs: Vec<PathBuf>,
+    /// Maximum number of routes rendered at the same time (1 renders sequentially)
+    pub concurrency: usize,
+    /// Path prefix the site is deployed under (e.g. `my-repo` on GitHub Pages); pages are
+    /// written below it and receive it as `SsgRenderContext::base_path`
+    pub path_prefix: String,
+    /// Skip writing pages whose inputs did not change since the last build
+    pub incremental: bool,
+    /// Render hydratable markup and embed the render context, so the client can hydrate
+    /// the pages instead of rendering them again
+    pub hydration: bool,
+    /// What to do when a route fails to generate
+    pub failure_policy: FailurePolicy,
+    /// Site-level `robots.txt`, written when set
+    pub robots_txt: Option<RobotsTxt>,
+    /// Additional static files (e.g. `.well-known/security.txt`), keyed by output path
+    pub site_files: BTreeMap<PathBuf, String>,
+    /// RSS, Atom and JSON feeds built from the generated pages
+    pub feeds: Vec<FeedConfig>,
+    /// File naming and trailing slash policy of the generated pages
+    pub output_layout: OutputLayout,
+    /// Redirects from old paths, written as HTML stubs and host manifests
+    pub redirects: Redirects,
+    /// Settings of the internal link check, see
+    /// [`StaticSiteGenerator::check_links`](crate::StaticSiteGenerator::check_links)
+    pub link_check: Option<LinkCheck>,
+    /// Pre-compressed siblings of the output files, see
+    /// [`StaticSiteGenerator::compress_outputs`](crate::StaticSiteGenerator::compress_outputs)
+    pub precompress: Option<Precompress>,
 }
 
 impl SsgConfig {
@@ -192,18 +248,72 @@ impl SsgConfig {
             metadata.insert(format!("param_{}", param_name), param_value.clone());
 
             // Add any parameter-specific metadata if available
-            if let Some(route_params) = self.route_params.get(route_pattern) {
-                if let Some(param_metadata) =
+            if let Some(route_params) = self.route_params.get(route_pattern)
+                && let Some(param_metadata) =
                     route_params.get_param_metadata(param_name, param_value)
-                {
-                    metadata.extend(param_metadata.clone());
-                }
+            {
+                metadata.extend(param_metadata.clone());
             }
         }
 
         metadata
     }
 
+    /// The configured `CanonicalLinkGenerator`, or a default one
+    pub(crate) fn canonical_link_generator(&self) -> CanonicalLinkGenerator {
+        self.generators
+            .iter()
+            .find_map(|generator| {
+                generator
+                    .as_any()
+                    .downcast_ref::<CanonicalLinkGenerator>()
+                    .cloned()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Assets base directory, from the `assets_base_dir` field or the global metadata
+    pub(crate) fn assets_dir(&self) -> Option<String> {
+        // Use either the direct assets_base_dir field, or check global_metadata for
+        // either "assets_base_dir" or legacy "json_ld_base_dir" (for backward compatibility)
+        self.assets_base_dir.clone().or_else(|| {
+            self.global_metadata
+                .get("assets_base_dir")
+                .or_else(|| self.global_metadata.get("json_ld_base_dir"))
+                .cloned()
+        })
+    }
+
+    /// Site domain from the global metadata or the canonical link generator
+    pub(crate) fn site_domain(&self) -> Option<String> {
+        self.global_metadata
+            .get("domain")
+            .cloned()
+            .or_else(|| self.canonical_link_generator().default_domain)
+    }
+
+    /// Languages of the site, from the `alternate_languages` global metadata, or the default
+    /// language if none are listed
+    pub(crate) fn languages(&self) -> Vec<String> {
+        let languages: Vec<String> = self
+            .global_metadata
+            .get("alternate_languages")
+            .map(|langs| {
+                langs
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|lang| !lang.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if languages.is_empty() {
+            vec![self.canonical_link_generator().default_language]
+        } else {
+            languages
+        }
+    }
+
     /// Add default generators if none have been added
     pub fn with_default_generators(mut self) -> Self {
         if self.generators.is_empty() {
@@ -239,16 +349,7 @@ impl SsgConfig {
             // JSON-LD generator (now with assets base directory)
             let mut json_ld_generator = JsonLdGenerator::new();
 
-            // Use either the direct assets_base_dir field, or check global_metadata for
-            // either "assets_base_dir" or legacy "json_ld_base_dir" (for backward compatibility)
-            let assets_dir = self.assets_base_dir.clone().or_else(|| {
-                self.global_metadata
-                    .get("assets_base_dir")
-                    .or_else(|| self.global_metadata.get("json_ld_base_dir"))
-                    .cloned()
-            });
-
-            if let Some(dir) = assets_dir {
+            if let Some(dir) = self.assets_dir() {
                 json_ld_generator.json_ld_base_dir = Some(dir);
             }
 
@@ -278,12 +379,31 @@ impl Default for SsgConfig {
             output_dir: PathBuf::from("dist"),
             template_path: None,
             default_template: String::new(),
+            templates_dir: None,
+            templates: BTreeMap::new(),
+            template_functions: TemplateFunctions::default(),
             global_metadata: HashMap::new(),
             route_metadata: HashMap::new(),
             generators: GeneratorCollection::new(),
             processors: ProcessorCollection::new(),
+            plugins: Vec::new(),
             route_params: HashMap::new(),
+            route_data: Vec::new(),
             assets_base_dir: None,
+            assets: None,
+            content_dirs: Vec::new(),
+            concurrency: 1,
+            path_prefix: String::new(),
+            incremental: false,
+            hydration: false,
+            failure_policy: FailurePolicy::default(),
+            robots_txt: None,
+            site_files: BTreeMap::new(),
+            feeds: Vec::new(),
+            output_layout: OutputLayout::default(),
+            redirects: Redirects::default(),
+            link_check: None,
+            precompress: None,
         }
         // Don't add defaults here to allow more control
     }
@@ -293,6 +413,14 @@ pub struct SsgConfigBuilder {
     pub config: SsgConfig,
     pub use_default_generators: bool,
     pub use_default_processors: bool,
+    /// Minification, run after all other processors
+    pub minify: Option<MinifyProcessor>,
+}
+
+impl Default for SsgConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SsgConfigBuilder {
@@ -301,6 +429,7 @@ impl SsgConfigBuilder {
             config: SsgConfig::default(),
             use_default_generators: true,
             use_default_processors: true,
+            minify: None,
         }
     }
 
@@ -329,6 +458,49 @@ impl SsgConfigBuilder {
         self
     }
 
+    /// Load every file in `path` as a named template (e.g. `docs.html`), usable as a layout
+    /// and from `include` / `extends`
+    pub fn templates_dir<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.templates_dir = Some(path.into());
+        self
+    }
+
+    /// Register a named template, usable as a layout and from `include` / `extends`
+    pub fn named_template(mut self, name: &str, source: &str) -> Self {
+        self.config
+            .templates
+            .insert(name.to_string(), source.to_string());
+        self
+    }
+
+    /// Register a template filter, e.g. `date` for `{{ date_published | date("%d.%m.%Y") }}`
+    pub fn template_filter<F, Rv, Args>(mut self, name: &str, filter: F) -> Self
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        self.config.template_functions.add_filter(name, filter);
+        self
+    }
+
+    /// Register a template function, e.g. `t` for `{{ t("greeting") }}`
+    pub fn template_function<F, Rv, Args>(mut self, name: &str, function: F) -> Self
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        self.config.template_functions.add_function(name, function);
+        self
+    }
+
+    /// Set a global template variable
+    pub fn template_global<V: Into<Value>>(mut self, name: &str, value: V) -> Self {
+        self.config.template_functions.add_global(name, value);
+        self
+    }
+
     pub fn global_metadata(mut self, metadata: HashMap<String, String>) -> Self {
         self.config.global_metadata = metadata;
         self
@@ -354,6 +526,101 @@ impl SsgConfigBuilder {
         self
     }
 
+    /// Copy static files into the output directory and rewrite references to them
+    pub fn assets(mut self, pipeline: AssetPipeline) -> Self {
+        self.config.assets = Some(pipeline);
+        self
+    }
+
+    /// Add a directory read by components while rendering; watch mode re-renders all pages
+    /// when a file in it changes
+    pub fn content_dir<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.content_dirs.push(path.into());
+        self
+    }
+
+    /// Render up to `limit` routes concurrently (values below 1 are treated as 1)
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.config.concurrency = limit.max(1);
+        self
+    }
+
+    /// Deploy the site under a path prefix (e.g. `my-repo` for a GitHub Pages project site)
+    pub fn path_prefix(mut self, prefix: &str) -> Self {
+        self.config.path_prefix = prefix.to_string();
+        self
+    }
+
+    /// Enable incremental builds backed by a manifest in the output directory
+    pub fn incremental(mut self, enabled: bool) -> Self {
+        self.config.incremental = enabled;
+        self
+    }
+
+    /// Render pages the client can hydrate with `yew_router::hydrate`
+    pub fn hydration(mut self, enabled: bool) -> Self {
+        self.config.hydration = enabled;
+        self
+    }
+
+    /// Set whether a failing route aborts the build or the remaining routes are still generated
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.config.failure_policy = policy;
+        self
+    }
+
+    /// Write a `robots.txt` with the given rules
+    pub fn robots_txt(mut self, robots_txt: RobotsTxt) -> Self {
+        self.config.robots_txt = Some(robots_txt);
+        self
+    }
+
+    /// Check internal links with the given settings
+    pub fn link_check(mut self, link_check: LinkCheck) -> Self {
+        self.config.link_check = Some(link_check);
+        self
+    }
+
+    /// Write gzip and brotli siblings of the output files with the given settings
+    pub fn precompress(mut self, precompress: Precompress) -> Self {
+        self.config.precompress = Some(precompress);
+        self
+    }
+
+    /// Write a static file at `path` (relative to the output directory)
+    pub fn site_file<P: Into<PathBuf>>(mut self, path: P, content: &str) -> Self {
+        self.config
+            .site_files
+            .insert(path.into(), content.to_string());
+        self
+    }
+
+    /// Publish a feed of the pages matching the feed's route pattern or prefix
+    pub fn feed(mut self, feed: FeedConfig) -> Self {
+        self.config.feeds.push(feed);
+        self
+    }
+
+    /// Set how output files are named and how links and canonical URLs end
+    pub fn output_layout(mut self, layout: OutputLayout) -> Self {
+        self.config.output_layout = layout;
+        self
+    }
+
+    /// Redirect an old path to a new location
+    pub fn redirect(mut self, redirect: Redirect) -> Self {
+        self.config.redirects.rules.push(redirect);
+        self
+    }
+
+    /// Also write the redirect manifest of the given host
+    pub fn redirect_host(mut self, host: RedirectHost) -> Self {
+        if !self.config.redirects.hosts.contains(&host) {
+            self.config.redirects.hosts.push(host);
+        }
+        self
+    }
+
     /// Add a parameter with values to a route pattern
     pub fn add_route_param<I, S>(mut self, route_pattern: &str, param_name: &str, values: I) -> Self
     where
@@ -364,7 +631,7 @@ impl SsgConfigBuilder {
             .config
             .route_params
             .entry(route_pattern.to_string())
-            .or_insert_with(RouteParams::new);
+            .or_default();
 
         route_params.add_param(param_name, values);
         self
@@ -382,12 +649,24 @@ impl SsgConfigBuilder {
             .config
             .route_params
             .entry(route_pattern.to_string())
-            .or_insert_with(RouteParams::new);
+            .or_default();
 
         route_params.add_param_metadata(param_name, param_value, metadata);
         self
     }
 
+    /// Load the data of the routes matching `route_pattern` before rendering them
+    pub fn route_data<P: RouteDataProvider + 'static>(
+        mut self,
+        route_pattern: &str,
+        provider: P,
+    ) -> Self {
+        self.config
+            .route_data
+            .push(RouteData::new(route_pattern, provider));
+        self
+    }
+
     pub fn add_generator<G: Generator + 'static>(mut self, generator: G) -> Self {
         self.config.generators.add(generator);
         self
@@ -398,6 +677,18 @@ impl SsgConfigBuilder {
         self
     }
 
+    /// Minify the generated pages after all other processors have run
+    pub fn minify(mut self, minify: MinifyProcessor) -> Self {
+        self.minify = Some(minify);
+        self
+    }
+
+    /// Register a plugin with hooks around the build and every route
+    pub fn add_plugin<P: BuildPlugin + 'static>(mut self, plugin: P) -> Self {
+        self.config.plugins.push(Box::new(plugin));
+        self
+    }
+
     pub fn build(self) -> SsgConfig {
         let mut config = self.config;
 
@@ -410,6 +701,27 @@ impl SsgConfigBuilder {
             config = config.with_default_processors();
         }
 
+        if let Some(minify) = self.minify {
+            config.processors.add(minify);
+        }
+
+        // Announce configured feeds in the page head
+        let has_feed_links = config
+            .generators
+            .iter()
+            .any(|g| g.as_any().is::<FeedLinkGenerator>());
+        if !config.feeds.is_empty() && !has_feed_links {
+            let mut feed_links = FeedLinkGenerator::new(config.feeds.clone());
+            if let Some(canonical) = config
+                .generators
+                .iter()
+                .find_map(|g| g.as_any().downcast_ref::<CanonicalLinkGenerator>())
+            {
+                feed_links.default_language = canonical.default_language.clone();
+            }
+            config.generators.add(feed_links);
+        }
+
         config
     }
 }
@@ -448,15 +760,9 @@ mod tests {
         );
 
         SsgConfig {
-            output_dir: PathBuf::from("dist"),
-            template_path: None,
-            default_template: String::new(),
             global_metadata: global,
             route_metadata,
-            generators: GeneratorCollection::new(),
-            processors: ProcessorCollection::new(),
-            route_params: HashMap::new(),
-            assets_base_dir: None,
+            ..SsgConfig::default()
         }
     }
 
@@ -499,6 +805,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unnecessary_get_then_check)]
     fn test_nonexistent_route_metadata() {
         let config = make_config();
         let meta = config.get_metadata_for_route("/foo/bar");
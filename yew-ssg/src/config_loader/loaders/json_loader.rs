@@ -1,20 +1,33 @@
 use crate::config_loader::model::SsgFileConfig;
 use crate::config_loader::{ConfigLoader, SsgConfig};
-use std::error::Error;
+use crate::error::{IoContext, SsgError};
 use std::path::Path;
 
 /// JSON implementation of ConfigLoader trait
 pub struct JsonLoader;
 
+impl JsonLoader {
+    fn parse(content: &str, file: Option<&Path>) -> Result<SsgConfig, SsgError> {
+        let file_config: SsgFileConfig =
+            serde_json::from_str(content).map_err(|error| SsgError::ConfigParse {
+                file: file.map(Path::to_path_buf),
+                line: Some(error.line()).filter(|line| *line > 0),
+                column: Some(error.column()).filter(|column| *column > 0),
+                message: error.to_string(),
+            })?;
+        Ok(file_config.to_ssg_config())
+    }
+}
+
 impl ConfigLoader for JsonLoader {
-    fn load_from_path<P: AsRef<Path>>(path: P) -> Result<SsgConfig, Box<dyn Error>> {
-        let content = std::fs::read_to_string(path)?;
-        Self::load_from_str(&content)
+    fn load_from_path<P: AsRef<Path>>(path: P) -> Result<SsgConfig, SsgError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_path(path)?;
+        Self::parse(&content, Some(path))
     }
 
-    fn load_from_str(content: &str) -> Result<SsgConfig, Box<dyn Error>> {
-        let file_config: SsgFileConfig = serde_json::from_str(content)?;
-        Ok(file_config.to_ssg_config())
+    fn load_from_str(content: &str) -> Result<SsgConfig, SsgError> {
+        Self::parse(content, None)
     }
 
     fn supported_extensions() -> Vec<&'static str> {
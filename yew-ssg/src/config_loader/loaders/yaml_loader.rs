@@ -1,20 +1,35 @@
 use crate::config_loader::model::SsgFileConfig;
 use crate::config_loader::{ConfigLoader, SsgConfig};
-use std::error::Error;
+use crate::error::{IoContext, SsgError};
 use std::path::Path;
 
 /// YAML implementation of ConfigLoader trait
 pub struct YamlLoader;
 
+impl YamlLoader {
+    fn parse(content: &str, file: Option<&Path>) -> Result<SsgConfig, SsgError> {
+        let file_config: SsgFileConfig = serde_yaml::from_str(content).map_err(|error| {
+            let location = error.location();
+            SsgError::ConfigParse {
+                file: file.map(Path::to_path_buf),
+                line: location.as_ref().map(|location| location.line()),
+                column: location.as_ref().map(|location| location.column()),
+                message: error.to_string(),
+            }
+        })?;
+        Ok(file_config.to_ssg_config())
+    }
+}
+
 impl ConfigLoader for YamlLoader {
-    fn load_from_path<P: AsRef<Path>>(path: P) -> Result<SsgConfig, Box<dyn Error>> {
-        let content = std::fs::read_to_string(path)?;
-        Self::load_from_str(&content)
+    fn load_from_path<P: AsRef<Path>>(path: P) -> Result<SsgConfig, SsgError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_path(path)?;
+        Self::parse(&content, Some(path))
     }
 
-    fn load_from_str(content: &str) -> Result<SsgConfig, Box<dyn Error>> {
-        let file_config: SsgFileConfig = serde_yaml::from_str(content)?;
-        Ok(file_config.to_ssg_config())
+    fn load_from_str(content: &str) -> Result<SsgConfig, SsgError> {
+        Self::parse(content, None)
     }
 
     fn supported_extensions() -> Vec<&'static str> {
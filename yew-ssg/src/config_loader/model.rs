@@ -1,8 +1,18 @@
+use crate::assets::AssetPipeline;
 use crate::config::{SsgConfig, SsgConfigBuilder};
 use crate::config_loader::RouteParams;
+use crate::failure::FailurePolicy;
+use crate::feeds::FeedConfig;
+use crate::link_check::LinkCheck;
+use crate::precompress::Precompress;
+use crate::processors::MinifyProcessor;
+use crate::redirects::Redirects;
+use crate::robots_txt::RobotsTxt;
+use crate::route_data::FileData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use yew_router::OutputLayout;
 
 /// Main configuration structure for the static site generator
 /// Used as the intermediate format between file formats and SsgConfig
@@ -23,6 +33,42 @@ pub struct SsgFileConfig {
     /// Parameter-based route configurations
     #[serde(default)]
     pub parameterized_routes: Vec<ParameterizedRouteConfig>,
+
+    /// Data files loaded for the routes matching a pattern
+    #[serde(default)]
+    pub route_data: Vec<RouteDataConfig>,
+
+    /// Site-level robots.txt rules (no robots.txt is written when omitted)
+    #[serde(default)]
+    pub robots_txt: Option<RobotsTxt>,
+
+    /// Well-known text files written next to the pages
+    #[serde(default)]
+    pub site_files: SiteFilesConfig,
+
+    /// RSS, Atom and JSON feeds
+    #[serde(default)]
+    pub feeds: Vec<FeedConfig>,
+
+    /// Redirects from old paths and the host manifests to write for them
+    #[serde(default)]
+    pub redirects: Redirects,
+
+    /// Settings of the internal link check
+    #[serde(default)]
+    pub link_check: Option<LinkCheck>,
+
+    /// Pre-compressed gzip and brotli siblings of the output files (none when omitted)
+    #[serde(default)]
+    pub precompress: Option<Precompress>,
+
+    /// Static files copied into the output directory (nothing is copied when omitted)
+    #[serde(default)]
+    pub assets: Option<AssetPipeline>,
+
+    /// Minification of the generated pages (pages are not minified when omitted)
+    #[serde(default)]
+    pub minify: Option<MinifyProcessor>,
 }
 
 /// General configuration options
@@ -39,12 +85,19 @@ pub struct GeneralConfig {
     #[serde(default)]
     pub default_template: String,
 
+    /// Directory of named layouts and partials, selected per route by the `layout` metadata key
+    pub templates_dir: Option<PathBuf>,
+
     /// Base directory for asset files
     pub assets_base_dir: Option<String>,
 
     /// Base directory for JSON-LD files
     pub json_ld_base_dir: Option<String>,
 
+    /// Directories read by components while rendering, watched for changes in watch mode
+    #[serde(default)]
+    pub content_dirs: Vec<PathBuf>,
+
     /// Default site name
     #[serde(default = "default_site_name")]
     pub site_name: String,
@@ -71,6 +124,38 @@ pub struct GeneralConfig {
     /// Default language code used for canonical URLs (default: "en")
     #[serde(default = "default_language")]
     pub default_language: String,
+
+    /// Maximum number of routes rendered concurrently (default: 1, sequential)
+    pub concurrency: Option<usize>,
+
+    /// Path prefix the site is deployed under, e.g. a GitHub Pages sub directory (default: none)
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+
+    /// Only rewrite pages whose inputs changed since the last build (default: false)
+    pub incremental: Option<bool>,
+
+    /// Render hydratable pages with the embedded render context (default: false)
+    pub hydration: Option<bool>,
+
+    /// `fail_fast` (default), `continue_and_report` or `skip_route`
+    pub failure_policy: Option<FailurePolicy>,
+
+    /// Output file naming and trailing slash policy (default: `about/index.html`, paths as routed)
+    pub output_layout: Option<OutputLayout>,
+}
+
+/// Contents of optional crawl-facing text files
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SiteFilesConfig {
+    /// Written to `/.well-known/security.txt`
+    pub security_txt: Option<String>,
+
+    /// Written to `/humans.txt`
+    pub humans_txt: Option<String>,
+
+    /// Written to `/ads.txt`
+    pub ads_txt: Option<String>,
 }
 
 /// Configuration for a specific route
@@ -102,6 +187,17 @@ pub struct ParameterizedRouteConfig {
     pub metadata: HashMap<String, String>,
 }
 
+/// Data file loaded for the routes matching a pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDataConfig {
+    /// Route pattern (e.g., "/crate/:id")
+    pub pattern: String,
+
+    /// JSON or YAML file; `{name}` placeholders are replaced with the route parameters and
+    /// `{lang}` with the page language (e.g., "content/crates/{id}.yaml")
+    pub file: String,
+}
+
 /// Definition of a parameter and its valid values
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParameterDefinition {
@@ -150,6 +246,10 @@ impl SsgFileConfig {
             builder = builder.template(template_path.clone());
         }
 
+        if let Some(templates_dir) = &self.general.templates_dir {
+            builder = builder.templates_dir(templates_dir.clone());
+        }
+
         // Set default template content if provided
         if !self.general.default_template.is_empty() {
             builder = builder.default_template_string(self.general.default_template.clone());
@@ -163,6 +263,81 @@ impl SsgFileConfig {
             builder = builder.assets_base_dir(json_ld_dir);
         }
 
+        for dir in &self.general.content_dirs {
+            builder = builder.content_dir(dir.clone());
+        }
+
+        if let Some(concurrency) = self.general.concurrency {
+            builder = builder.concurrency(concurrency);
+        }
+
+        if let Some(prefix) = &self.general.path_prefix {
+            builder = builder.path_prefix(prefix);
+        }
+
+        if let Some(incremental) = self.general.incremental {
+            builder = builder.incremental(incremental);
+        }
+
+        if let Some(hydration) = self.general.hydration {
+            builder = builder.hydration(hydration);
+        }
+
+        if let Some(policy) = self.general.failure_policy {
+            builder = builder.failure_policy(policy);
+        }
+
+        if let Some(layout) = self.general.output_layout {
+            builder = builder.output_layout(layout);
+        }
+
+        if let Some(robots_txt) = &self.robots_txt {
+            builder = builder.robots_txt(robots_txt.clone());
+        }
+
+        // Add well-known files
+        let site_files = [
+            (".well-known/security.txt", &self.site_files.security_txt),
+            ("humans.txt", &self.site_files.humans_txt),
+            ("ads.txt", &self.site_files.ads_txt),
+        ];
+        for (path, content) in site_files {
+            if let Some(content) = content {
+                builder = builder.site_file(path, content);
+            }
+        }
+
+        for feed in &self.feeds {
+            builder = builder.feed(feed.clone());
+        }
+
+        for redirect in &self.redirects.rules {
+            builder = builder.redirect(redirect.clone());
+        }
+        for host in &self.redirects.hosts {
+            builder = builder.redirect_host(*host);
+        }
+
+        for data in &self.route_data {
+            builder = builder.route_data(&data.pattern, FileData::new(&data.file));
+        }
+
+        if let Some(assets) = &self.assets {
+            builder = builder.assets(assets.clone());
+        }
+
+        if let Some(link_check) = &self.link_check {
+            builder = builder.link_check(link_check.clone());
+        }
+
+        if let Some(precompress) = &self.precompress {
+            builder = builder.precompress(precompress.clone());
+        }
+
+        if let Some(minify) = &self.minify {
+            builder = builder.minify(minify.clone());
+        }
+
         // Set global metadata
         let mut global_metadata = self.global_metadata.clone();
 
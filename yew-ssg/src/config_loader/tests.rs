@@ -1,13 +1,20 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
+    use crate::assets::AssetPipeline;
     use crate::config_loader::{
         loader::{ConfigLoader, load_config},
         loaders::{JsonLoader, YamlLoader},
         model::{
             CanonicalBehavior, GeneralConfig, ParameterDefinition, ParameterVariant,
-            ParameterizedRouteConfig, RouteConfig, SsgFileConfig,
+            ParameterizedRouteConfig, RouteConfig, RouteDataConfig, SiteFilesConfig, SsgFileConfig,
         },
     };
+    use crate::failure::FailurePolicy;
+    use crate::link_check::LinkCheck;
+    use crate::precompress::Precompress;
+    use crate::processors::MinifyProcessor;
+    use crate::robots_txt::RobotsTxt;
     use std::collections::HashMap;
     use std::error::Error;
     use std::io::Write;
@@ -290,13 +297,21 @@ routes:
                 template_path: Some(PathBuf::from("template.html")),
                 assets_base_dir: None,
                 json_ld_base_dir: None,
+                content_dirs: vec![PathBuf::from("content")],
                 default_template: "<html>{{ content }}</html>".to_string(),
+                templates_dir: Some(PathBuf::from("templates")),
                 site_name: "Conversion Test".to_string(),
                 title_format: "{title} - {site_name}".to_string(),
                 default_image: Some("/images/default.jpg".to_string()),
                 twitter_handle: Some("testhandle".to_string()),
                 canonical_to_default_langs: Some(CanonicalBehavior::Boolean(true)),
                 default_language: "en".to_string(),
+                concurrency: Some(4),
+                path_prefix: Some("docs".to_string()),
+                incremental: Some(true),
+                hydration: Some(true),
+                failure_policy: Some(FailurePolicy::SkipRoute),
+                output_layout: None,
             },
             global_metadata: HashMap::from([
                 ("lang".to_string(), "en".to_string()),
@@ -324,6 +339,21 @@ routes:
                     },
                 ],
             }],
+            route_data: vec![RouteDataConfig {
+                pattern: "/item/:id".to_string(),
+                file: "content/items/{id}.json".to_string(),
+            }],
+            robots_txt: Some(RobotsTxt::default()),
+            site_files: SiteFilesConfig {
+                ads_txt: Some("example.com, pub-0000, DIRECT".to_string()),
+                ..SiteFilesConfig::default()
+            },
+            feeds: Vec::new(),
+            redirects: Default::default(),
+            link_check: Some(LinkCheck::default().fail_on_broken(true)),
+            precompress: Some(Precompress::gzip_only().min_size(512)),
+            minify: Some(MinifyProcessor::new()),
+            assets: Some(AssetPipeline::new("public").fingerprint(true)),
         };
 
         // Convert to SsgConfig
@@ -333,6 +363,26 @@ routes:
         assert_eq!(config.output_dir, PathBuf::from("conversion_test"));
         assert_eq!(config.template_path, Some(PathBuf::from("template.html")));
         assert_eq!(config.default_template, "<html>{{ content }}</html>");
+        assert_eq!(config.templates_dir, Some(PathBuf::from("templates")));
+        assert_eq!(config.content_dirs, vec![PathBuf::from("content")]);
+        assert_eq!(config.concurrency, 4);
+        assert_eq!(config.path_prefix, "docs");
+        assert!(config.incremental);
+        assert!(config.hydration);
+        assert_eq!(config.failure_policy, FailurePolicy::SkipRoute);
+        assert_eq!(config.robots_txt, Some(RobotsTxt::default()));
+        assert!(config.link_check.unwrap().fail_on_broken);
+        assert_eq!(config.precompress.unwrap().min_size, 512);
+        assert_eq!(config.route_data[0].pattern, "/item/:id");
+        assert!(config.assets.unwrap().fingerprint);
+        assert_eq!(
+            config.processors.iter().last().unwrap().name(),
+            "minify_processor"
+        );
+        assert_eq!(
+            config.site_files.get(Path::new("ads.txt")).unwrap(),
+            "example.com, pub-0000, DIRECT"
+        );
 
         // Verify global metadata
         assert_eq!(config.global_metadata.get("lang").unwrap(), "en");
@@ -385,6 +435,126 @@ routes:
         );
     }
 
+    #[test]
+    fn test_yaml_robots_txt_and_site_files() {
+        let yaml_str = r#"
+robots_txt:
+  sitemap: false
+  rules:
+    - user_agent: "*"
+      disallow: ["/drafts/"]
+site_files:
+  security_txt: "Contact: mailto:security@example.com"
+  humans_txt: "Team: yew-ssg"
+"#;
+
+        let config = YamlLoader::load_from_str(yaml_str).unwrap();
+
+        let robots = config.robots_txt.unwrap();
+        assert!(!robots.sitemap);
+        assert_eq!(robots.rules[0].user_agent, "*");
+        assert_eq!(robots.rules[0].disallow, vec!["/drafts/"]);
+        assert!(robots.rules[0].allow.is_empty());
+
+        assert_eq!(
+            config
+                .site_files
+                .get(Path::new(".well-known/security.txt"))
+                .unwrap(),
+            "Contact: mailto:security@example.com"
+        );
+        assert_eq!(
+            config.site_files.get(Path::new("humans.txt")).unwrap(),
+            "Team: yew-ssg"
+        );
+        assert!(!config.site_files.contains_key(Path::new("ads.txt")));
+    }
+
+    #[test]
+    fn test_yaml_feeds() {
+        let yaml_str = r#"
+feeds:
+  - title: "Blog"
+    pattern: "/blog/:id"
+    output_dir: "blog"
+    formats: ["rss", "atom", "json"]
+    per_language: true
+    limit: 20
+"#;
+
+        let config = YamlLoader::load_from_str(yaml_str).unwrap();
+
+        let feed = &config.feeds[0];
+        assert_eq!(feed.title, "Blog");
+        assert_eq!(feed.pattern.as_deref(), Some("/blog/:id"));
+        assert_eq!(feed.formats.len(), 3);
+        assert!(feed.per_language);
+        assert_eq!(feed.limit, Some(20));
+        assert!(config.generators.iter().any(|g| g.name() == "feed_links"));
+    }
+
+    #[test]
+    fn test_yaml_output_layout() {
+        let yaml_str = r#"
+general:
+  output_layout:
+    file_naming: both
+    trailing_slash: never
+"#;
+
+        let config = YamlLoader::load_from_str(yaml_str).unwrap();
+        assert_eq!(
+            config.output_layout,
+            yew_router::OutputLayout::new(
+                yew_router::FileNaming::Both,
+                yew_router::TrailingSlash::Never
+            )
+        );
+
+        // Omitted fields keep their defaults
+        let config =
+            YamlLoader::load_from_str("general:\n  output_layout:\n    trailing_slash: always\n")
+                .unwrap();
+        assert_eq!(
+            config.output_layout,
+            yew_router::OutputLayout::new(
+                yew_router::FileNaming::DirectoryIndex,
+                yew_router::TrailingSlash::Always
+            )
+        );
+
+        let config = YamlLoader::load_from_str("general: {}").unwrap();
+        assert_eq!(config.output_layout, yew_router::OutputLayout::default());
+    }
+
+    #[test]
+    fn test_yaml_redirects() {
+        let yaml_str = r#"
+redirects:
+  hosts: ["netlify", "apache"]
+  rules:
+    - from: "/old-about"
+      to: "/about"
+    - from: "/blog"
+      to: "https://blog.example.com/"
+      status: 302
+"#;
+
+        let config = YamlLoader::load_from_str(yaml_str).unwrap();
+        let redirects = &config.redirects;
+        assert_eq!(redirects.rules.len(), 2);
+        assert_eq!(redirects.rules[0].status, 301);
+        assert_eq!(redirects.rules[1].status, 302);
+        assert!(redirects.rules[1].is_external());
+        assert_eq!(
+            redirects.hosts,
+            vec![
+                crate::redirects::RedirectHost::Netlify,
+                crate::redirects::RedirectHost::Apache
+            ]
+        );
+    }
+
     #[test]
     fn test_unsupported_extension() {
         // Try to load a file with an unsupported extension
@@ -400,6 +570,16 @@ routes:
         let invalid_yaml = "general: {"; // Invalid YAML - corrected semicolon
         let result = YamlLoader::load_from_str(invalid_yaml);
         assert!(result.is_err());
+
+        let err = YamlLoader::load_from_str("general:\n  concurrency: many\n").unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::SsgError::ConfigParse {
+                line: Some(2),
+                file: None,
+                ..
+            }
+        ));
     }
 
     #[test]
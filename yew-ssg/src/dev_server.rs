@@ -0,0 +1,564 @@
+//! HTTP handling of the local dev server.
+//!
+//! [`StaticSiteGenerator::serve`] renders pages on request through the regular pipeline. This
+//! module reads requests, answers them through a [`DevSite`], resolves static files below the
+//! output directory and injects the live-reload snippet into served pages. The server handles
+//! one request at a time and is meant for local previews only.
+
+use crate::assets::AssetManifest;
+use crate::config::SsgConfig;
+use crate::error::SsgError;
+use crate::paths::{match_pattern, percent_decode};
+use crate::static_site_generator::{PageJob, StaticSiteGenerator};
+use futures::channel::mpsc;
+use log::warn;
+use minijinja::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use strum::IntoEnumIterator;
+use yew::BaseComponent;
+use yew_router::Routable;
+
+/// Time a client gets to send its request head, so a stalled connection cannot hold up the server
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Path polled by the live-reload snippet
+pub(crate) const LIVE_RELOAD_PATH: &str = "/__yew_ssg/livereload";
+
+/// Reloads the page when the version served at [`LIVE_RELOAD_PATH`] changes, e.g. after a
+/// template edit or a restart of the server
+const LIVE_RELOAD_SCRIPT: &str = r#"<script>
+(function () {
+    var version = null;
+    setInterval(function () {
+        fetch("/__yew_ssg/livereload", { cache: "no-store" })
+            .then(function (response) { return response.text(); })
+            .then(function (current) {
+                if (version !== null && current !== version) { location.reload(); }
+                version = current;
+            })
+            .catch(function () {});
+    }, 1000);
+})();
+</script>"#;
+
+/// Request line of an HTTP request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Request {
+    pub method: String,
+    /// Decoded path without query string
+    pub path: String,
+}
+
+impl Request {
+    /// Read the request line and skip the headers
+    fn read(stream: &TcpStream) -> io::Result<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+        }
+        Self::parse(&request_line)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed request line"))
+    }
+
+    /// Parse a request line such as `GET /about?lang=de HTTP/1.1`
+    pub fn parse(request_line: &str) -> Option<Self> {
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let target = parts.next()?;
+        let path = target.split(['?', '#']).next().unwrap_or_default();
+        Some(Self {
+            method,
+            path: percent_decode(path),
+        })
+    }
+}
+
+/// Response of the dev server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// HTML page with the live-reload snippet
+    pub fn page(status: u16, html: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body: inject_live_reload(html).into_bytes(),
+        }
+    }
+
+    /// Plain text
+    pub fn text(status: u16, text: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: text.as_bytes().to_vec(),
+        }
+    }
+
+    /// Error page showing `message`, reloaded once the problem is fixed
+    pub fn error(status: u16, message: &str) -> Self {
+        let message = message
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        Self::page(
+            status,
+            &format!(
+                "<!DOCTYPE html><html><body><h1>{} {}</h1><pre>{}</pre></body></html>",
+                status,
+                reason(status),
+                message
+            ),
+        )
+    }
+
+    /// Contents of a static file
+    pub fn file(path: &Path, body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: content_type(path),
+            body,
+        }
+    }
+
+    /// Write status line, headers and (unless `head_only`) the body
+    pub fn write_to(&self, stream: &mut impl Write, head_only: bool) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len()
+        )?;
+        if !head_only {
+            stream.write_all(&self.body)?;
+        }
+        stream.flush()
+    }
+}
+
+/// Site answering the requests of [`StaticSiteGenerator::serve`]
+///
+/// Pages are planned once per live-reload version and reused until a source or output file
+/// changes.
+pub(crate) struct DevSite<'a> {
+    generator: &'a StaticSiteGenerator,
+    /// Start of the server, so a restart changes the live-reload version
+    started: Duration,
+    build: Option<DevBuild>,
+}
+
+/// Generator and planned pages of one live-reload version
+struct DevBuild {
+    version: String,
+    /// Fresh generator, so template edits are picked up
+    generator: StaticSiteGenerator,
+    jobs: Vec<PageJob>,
+    /// Template value listing the planned pages
+    routes: Value,
+}
+
+impl<'a> DevSite<'a> {
+    /// Serve the site of `generator`
+    pub fn new(generator: &'a StaticSiteGenerator) -> Self {
+        Self {
+            generator,
+            started: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default(),
+            build: None,
+        }
+    }
+
+    /// Answer a request with a rendered page, a static file or the live-reload version.
+    pub async fn respond<R, C>(&mut self, request: &Request) -> Response
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        if request.method != "GET" && request.method != "HEAD" {
+            return Response::text(405, "Only GET and HEAD are supported");
+        }
+        let version = self.live_reload_version();
+        if request.path == LIVE_RELOAD_PATH {
+            return Response::text(200, &version);
+        }
+
+        let config = &self.generator.config;
+        let base_path = format!("/{}", config.path_prefix.trim_matches('/'));
+        let path = match request.path.strip_prefix(&base_path) {
+            Some(path) if base_path != "/" && (path.is_empty() || path.starts_with('/')) => path,
+            _ => request.path.as_str(),
+        };
+
+        if is_asset_path(path) {
+            let output_dir = &config.output_dir;
+            return match static_file(output_dir, &request.path)
+                .or_else(|| static_file(output_dir, path))
+                .or_else(|| asset_source(config, path))
+            {
+                Some(file) => match fs::read(&file) {
+                    Ok(body) => Response::file(&file, body),
+                    Err(e) => Response::error(500, &SsgError::io(file, e).to_string()),
+                },
+                None => Response::error(404, &format!("{} not found", request.path)),
+            };
+        }
+
+        match self.render_request::<R, C>(&page_path(path), version).await {
+            Ok((status, html)) => Response::page(status, &html),
+            Err(e @ SsgError::RouteRecognition { .. }) => Response::error(404, &e.to_string()),
+            Err(e) => Response::error(500, &e.to_string()),
+        }
+    }
+
+    /// Render the page of a requested path with its status code.
+    ///
+    /// Fails with [`SsgError::RouteRecognition`] if no route matches.
+    async fn render_request<R, C>(
+        &mut self,
+        path: &str,
+        version: String,
+    ) -> Result<(u16, String), SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        let route = R::recognize(path).ok_or_else(|| SsgError::RouteRecognition {
+            path: path.to_string(),
+            pattern: None,
+        })?;
+        let route_path = route.to_path();
+
+        let build = self.build::<R>(version)?;
+        let unplanned;
+        let job = match build.jobs.iter().find(|job| job.route_path == route_path) {
+            Some(job) => job,
+            None => {
+                unplanned = unplanned_job::<R>(&build.generator, route_path);
+                &unplanned
+            }
+        };
+        let status = if job.not_found { 404 } else { 200 };
+
+        let page = build
+            .generator
+            .render_page::<C>(
+                &job.route_path,
+                job.context.clone(),
+                &job.metadata,
+                &build.routes,
+            )
+            .await?;
+        Ok((status, page.html))
+    }
+
+    /// Generator and pages of `version`, planned again when the version changed
+    fn build<R>(&mut self, version: String) -> Result<&DevBuild, SsgError>
+    where
+        R: Routable + IntoEnumIterator,
+    {
+        if let Some(build) = self.build.take().filter(|build| build.version == version) {
+            return Ok(self.build.insert(build));
+        }
+
+        let generator = StaticSiteGenerator::new(self.generator.config.clone())?;
+        let path_prefix = generator.path_prefix();
+        let mut jobs = generator.plan_routes::<R>(&path_prefix);
+        jobs.extend(generator.plan_parameterized_routes::<R>(&path_prefix, &mut Vec::new()));
+        let routes = generator.routes_value(&jobs);
+        Ok(self.build.insert(DevBuild {
+            version,
+            generator,
+            jobs,
+            routes,
+        }))
+    }
+
+    /// Changes whenever the server restarts or a source or output file changes.
+    fn live_reload_version(&self) -> String {
+        let config = &self.generator.config;
+        let mut stamps = HashMap::new();
+        for (path, _) in crate::watch::source_paths(config) {
+            crate::watch::collect_stamps(&path, &mut stamps);
+        }
+        crate::watch::collect_stamps(&config.output_dir, &mut stamps);
+        let mut stamps: Vec<_> = stamps.into_iter().collect();
+        stamps.sort();
+
+        let mut hasher = DefaultHasher::new();
+        self.started.hash(&mut hasher);
+        stamps.hash(&mut hasher);
+        format!("{:x}", hasher.finish())
+    }
+}
+
+/// Page of a recognized path that is not part of the build, e.g. a parameter value
+/// missing from the configuration.
+fn unplanned_job<R>(generator: &StaticSiteGenerator, route_path: String) -> PageJob
+where
+    R: Routable,
+{
+    let config = &generator.config;
+    let path_prefix = generator.path_prefix();
+    let params = R::routes()
+        .into_iter()
+        .find_map(|pattern| match_pattern(pattern, &route_path).map(|params| (pattern, params)))
+        .filter(|(_, params)| !params.is_empty());
+    let (pattern, params, mut metadata) = match params {
+        Some((pattern, params)) => {
+            let metadata = config.get_metadata_for_parameterized_route(pattern, &params);
+            (Some(pattern.to_string()), params, metadata)
+        }
+        None => (
+            None,
+            HashMap::new(),
+            config.get_metadata_for_route(&route_path),
+        ),
+    };
+    metadata.insert("path".to_string(), config.output_layout.href(&route_path));
+    if !path_prefix.is_empty() {
+        metadata.insert("path_prefix".to_string(), path_prefix.clone());
+    }
+
+    PageJob {
+        pattern,
+        ..PageJob::new(
+            route_path,
+            &path_prefix,
+            params,
+            metadata,
+            config.output_layout,
+        )
+    }
+}
+
+/// Source file of an asset requested by its (fingerprinted) output path, read from the
+/// pipeline's current files so assets need not be copied while serving.
+fn asset_source(config: &SsgConfig, path: &str) -> Option<PathBuf> {
+    let pipeline = config.assets.as_ref()?;
+    let manifest = AssetManifest::scan(pipeline).ok()?;
+    static_file(&pipeline.source_dir, manifest.source(path)?)
+}
+
+/// Accept connections and read their request heads on a background thread, so neither blocks
+/// the runtime. Clients get [`READ_TIMEOUT`] to send the head.
+pub(crate) fn incoming(listener: TcpListener) -> mpsc::UnboundedReceiver<(TcpStream, Request)> {
+    let (sender, receiver) = mpsc::unbounded();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            let request = stream
+                .set_read_timeout(Some(READ_TIMEOUT))
+                .and_then(|()| Request::read(&stream));
+            match request {
+                Ok(request) => {
+                    if sender.unbounded_send((stream, request)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => warn!("Invalid request: {}", e),
+            }
+        }
+    });
+    receiver
+}
+
+/// Insert the live-reload snippet before `</body>`, or append it
+pub(crate) fn inject_live_reload(html: &str) -> String {
+    match html.rfind("</body>") {
+        Some(index) => format!("{}{}{}", &html[..index], LIVE_RELOAD_SCRIPT, &html[index..]),
+        None => format!("{}{}", html, LIVE_RELOAD_SCRIPT),
+    }
+}
+
+/// File below `root` for a request path; `None` for paths leaving `root`
+pub(crate) fn static_file(root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path.trim_start_matches('/'));
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    let file = root.join(relative);
+    file.is_file().then_some(file)
+}
+
+/// Whether a request path names a file other than an HTML page (e.g. `/app.js`)
+pub(crate) fn is_asset_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or_default();
+    name.contains('.') && !name.ends_with(".html")
+}
+
+/// Route path of a page request: `/about/index.html` and `/about.html` become `/about`
+pub(crate) fn page_path(path: &str) -> String {
+    let path = path
+        .strip_suffix("index.html")
+        .or_else(|| path.strip_suffix(".html"))
+        .unwrap_or(path);
+    match path.trim_end_matches('/') {
+        "" => "/".to_string(),
+        path => path.to_string(),
+    }
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("") {
+        "html" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" | "jsonld" | "map" => "application/json",
+        "wasm" => "application/wasm",
+        "xml" => "application/xml",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+
+    #[test]
+    fn test_request_and_paths() {
+        let request = Request::parse("GET /crate/yew%2Dssg/?tab=1 HTTP/1.1\r\n").unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/crate/yew-ssg/");
+
+        assert_eq!(page_path("/crate/yew-ssg/"), "/crate/yew-ssg");
+        assert_eq!(page_path("/about/index.html"), "/about");
+        assert_eq!(page_path("/index.html"), "/");
+        assert!(is_asset_path("/app-1a2b.wasm"));
+        assert!(!is_asset_path("/about.html"));
+    }
+
+    #[tokio::test]
+    async fn test_incoming_reads_request_heads() {
+        use futures::StreamExt;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut connections = incoming(listener);
+
+        let mut client = TcpStream::connect(address).unwrap();
+        client
+            .write_all(b"GET /about HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let (stream, request) = connections.next().await.unwrap();
+        assert_eq!(request.path, "/about");
+        assert_eq!(stream.read_timeout().unwrap(), Some(READ_TIMEOUT));
+    }
+
+    #[test]
+    fn test_static_files_stay_in_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.js"), "").unwrap();
+
+        assert!(static_file(dir.path(), "/app.js").is_some());
+        assert!(static_file(dir.path(), "/../app.js").is_none());
+        assert!(static_file(dir.path(), "/missing.js").is_none());
+    }
+
+    #[test]
+    fn test_live_reload_injection() {
+        let html = inject_live_reload("<html><body><p>hi</p></body></html>");
+        assert!(html.contains("<p>hi</p><script>"));
+        assert!(html.ends_with("</script></body></html>"));
+        assert!(LIVE_RELOAD_SCRIPT.contains(LIVE_RELOAD_PATH));
+
+        let mut written = Vec::new();
+        Response::text(200, "v1")
+            .write_to(&mut written, false)
+            .unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(written.ends_with(
+            "Content-Length: 2\r\nCache-Control: no-store\r\nConnection: close\r\n\r\nv1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_serve_renders_pages_on_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .default_template_string("<html><body>{{ content | safe }}</body></html>".to_string())
+            .add_route_param("/crate/:id", "id", ["a"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        let mut site = DevSite::new(&generator);
+        let mut respond = async |line: &str| {
+            let request = Request::parse(line).unwrap();
+            site.respond::<ContextRoute, ContextProbe>(&request).await
+        };
+        let body = |response: &Response| String::from_utf8(response.body.clone()).unwrap();
+
+        let home = respond("GET / HTTP/1.1").await;
+        assert_eq!(home.status, 200);
+        assert!(body(&home).contains("path=/ id="));
+        assert!(body(&home).contains(LIVE_RELOAD_PATH));
+
+        // Recognized paths outside the configured parameters are rendered as well
+        let unplanned = respond("GET /crate/zzz/ HTTP/1.1").await;
+        assert!(body(&unplanned).contains("path=/crate/zzz id=zzz"));
+
+        assert_eq!(respond("GET /missing.css HTTP/1.1").await.status, 404);
+        assert_eq!(respond("GET /a/b/c HTTP/1.1").await.status, 404);
+        assert_eq!(respond("POST / HTTP/1.1").await.status, 405);
+
+        let version = body(&respond(&format!("GET {} HTTP/1.1", LIVE_RELOAD_PATH)).await);
+        fs::write(dir.path().join("app.js"), "console.log(1)").unwrap();
+        let asset = respond("GET /app.js HTTP/1.1").await;
+        assert_eq!(asset.content_type, "text/javascript; charset=utf-8");
+        assert_eq!(body(&asset), "console.log(1)");
+        assert_ne!(
+            body(&respond(&format!("GET {} HTTP/1.1", LIVE_RELOAD_PATH)).await),
+            version
+        );
+    }
+}
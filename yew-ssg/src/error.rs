@@ -0,0 +1,521 @@
+//! Error type of the static site generator.
+//!
+//! [`SsgError`] tells which step of a build failed and, where it applies, for which route and
+//! in which generator or processor. Custom generators and processors can return their own
+//! errors through [`SsgError::custom`]; the generator wraps them with the route and component
+//! name before handing them to the caller.
+
+use crate::link_check::LinkReport;
+use crate::report::GenerationReport;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Boxed error of a custom generator or processor
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Result type of the static site generator
+pub type Result<T, E = SsgError> = std::result::Result<T, E>;
+
+/// Errors returned by the static site generator
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SsgError {
+    /// The page template could not be read or compiled
+    TemplateLoad {
+        /// Template file, if the template was loaded from disk
+        path: Option<PathBuf>,
+        /// Underlying error
+        source: BoxError,
+    },
+    /// A component panicked while rendering a route
+    ComponentPanic {
+        /// Route of the page
+        route: String,
+        /// Panic message
+        message: String,
+    },
+    /// Rendering the page template failed
+    TemplateRender {
+        /// Route of the page
+        route: String,
+        /// Underlying error
+        source: minijinja::Error,
+    },
+    /// A generator failed
+    Generator {
+        /// Route of the page
+        route: String,
+        /// Name of the generator
+        generator: String,
+        /// Error returned by the generator
+        source: BoxError,
+    },
+    /// A processor failed
+    Processor {
+        /// Route of the page
+        route: String,
+        /// Name of the processor
+        processor: String,
+        /// Error returned by the processor
+        source: BoxError,
+    },
+    /// A build plugin failed
+    Plugin {
+        /// Route of the page, if the plugin failed in a route hook
+        route: Option<String>,
+        /// Name of the plugin
+        plugin: String,
+        /// Error returned by the plugin
+        source: BoxError,
+    },
+    /// The data provider of a route failed
+    RouteData {
+        /// Route of the page
+        route: String,
+        /// Route pattern the provider is registered for
+        pattern: String,
+        /// Error returned by the provider
+        source: BoxError,
+    },
+    /// A generator or processor was asked for an output key it does not provide
+    UnsupportedKey {
+        /// Name of the generator or processor
+        component: String,
+        /// Requested key
+        key: String,
+    },
+    /// Reading or writing a file failed
+    Io {
+        /// File or directory involved
+        path: PathBuf,
+        /// Underlying error
+        source: io::Error,
+    },
+    /// A configuration file could not be parsed
+    ConfigParse {
+        /// Configuration file, if loaded from disk
+        file: Option<PathBuf>,
+        /// Line of the error (1-based), if known
+        line: Option<usize>,
+        /// Column of the error (1-based), if known
+        column: Option<usize>,
+        /// Parser message
+        message: String,
+    },
+    /// The configuration is incomplete or not supported
+    Config(String),
+    /// A path is not recognized by the route enum
+    RouteRecognition {
+        /// Constructed path
+        path: String,
+        /// Route pattern the path was built from, if any
+        pattern: Option<String>,
+    },
+    /// A page would overwrite a file written earlier in the same run
+    OutputCollision {
+        /// Route of the page
+        route: String,
+        /// Output file
+        output_file: PathBuf,
+        /// Route that wrote the file first
+        existing_route: String,
+    },
+    /// Routes failed under [`FailurePolicy::ContinueAndReport`](crate::failure::FailurePolicy);
+    /// the report lists the generated pages and the failures
+    PagesFailed {
+        /// Report of the attempted routes
+        report: Box<GenerationReport>,
+    },
+    /// The link check found broken internal links and
+    /// [`LinkCheck::fail_on_broken`](crate::link_check::LinkCheck) is set
+    BrokenLinks {
+        /// Report of the link check
+        report: Box<LinkReport>,
+    },
+    /// Error of a custom generator, processor or other extension
+    Custom(BoxError),
+}
+
+impl SsgError {
+    /// Wrap an error of a custom generator or processor
+    pub fn custom(error: impl Into<BoxError>) -> Self {
+        SsgError::Custom(error.into())
+    }
+
+    /// Create an IO error for the given path
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        SsgError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Attribute the error to a generator running for a route
+    pub fn in_generator(self, route: &str, generator: &str) -> Self {
+        match self {
+            SsgError::Generator { .. } | SsgError::Processor { .. } | SsgError::Plugin { .. } => {
+                self
+            }
+            error => SsgError::Generator {
+                route: route.to_string(),
+                generator: generator.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Attribute the error to a processor running for a route
+    pub fn in_processor(self, route: &str, processor: &str) -> Self {
+        match self {
+            SsgError::Generator { .. } | SsgError::Processor { .. } | SsgError::Plugin { .. } => {
+                self
+            }
+            error => SsgError::Processor {
+                route: route.to_string(),
+                processor: processor.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Attribute the error to a build plugin, running for a route if given
+    pub fn in_plugin(self, route: Option<&str>, plugin: &str) -> Self {
+        match self {
+            SsgError::Generator { .. } | SsgError::Processor { .. } | SsgError::Plugin { .. } => {
+                self
+            }
+            error => SsgError::Plugin {
+                route: route.map(str::to_string),
+                plugin: plugin.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Attribute the error to the data provider registered for `pattern`, running for a route
+    pub fn in_route_data(self, route: &str, pattern: &str) -> Self {
+        match self {
+            SsgError::RouteData { .. } => self,
+            error => SsgError::RouteData {
+                route: route.to_string(),
+                pattern: pattern.to_string(),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Report of a run that attempted every route, so the build can go on with the pages
+    /// that were generated. Any other error is returned unchanged.
+    pub fn into_report(self) -> Result<GenerationReport> {
+        match self {
+            SsgError::PagesFailed { report } => Ok(*report),
+            error => Err(error),
+        }
+    }
+
+    /// Route the error occurred for, if any
+    pub fn route(&self) -> Option<&str> {
+        match self {
+            SsgError::ComponentPanic { route, .. }
+            | SsgError::TemplateRender { route, .. }
+            | SsgError::Generator { route, .. }
+            | SsgError::Processor { route, .. }
+            | SsgError::RouteData { route, .. }
+            | SsgError::OutputCollision { route, .. } => Some(route),
+            SsgError::Plugin { route, .. } => route.as_deref(),
+            SsgError::RouteRecognition { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Name of the generator or processor that failed (the route pattern for data providers),
+    /// if any
+    pub fn component(&self) -> Option<&str> {
+        match self {
+            SsgError::Generator { generator, .. } => Some(generator),
+            SsgError::Processor { processor, .. } => Some(processor),
+            SsgError::Plugin { plugin, .. } => Some(plugin),
+            SsgError::RouteData { pattern, .. } => Some(pattern),
+            SsgError::UnsupportedKey { component, .. } => Some(component),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SsgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsgError::TemplateLoad {
+                path: Some(path),
+                source,
+            } => write!(f, "Failed to load template {:?}: {}", path, source),
+            SsgError::TemplateLoad { path: None, source } => {
+                write!(f, "Failed to load template: {}", source)
+            }
+            SsgError::ComponentPanic { route, message } => {
+                write!(f, "Component panicked for route '{}': {}", route, message)
+            }
+            SsgError::TemplateRender { route, source } => {
+                write!(
+                    f,
+                    "Failed to render template for route '{}': {}",
+                    route, source
+                )
+            }
+            SsgError::Generator {
+                route,
+                generator,
+                source,
+            } => write!(
+                f,
+                "Generator '{}' failed for route '{}': {}",
+                generator, route, source
+            ),
+            SsgError::Processor {
+                route,
+                processor,
+                source,
+            } => write!(
+                f,
+                "Processor '{}' failed for route '{}': {}",
+                processor, route, source
+            ),
+            SsgError::Plugin {
+                route: Some(route),
+                plugin,
+                source,
+            } => write!(
+                f,
+                "Plugin '{}' failed for route '{}': {}",
+                plugin, route, source
+            ),
+            SsgError::Plugin {
+                route: None,
+                plugin,
+                source,
+            } => write!(f, "Plugin '{}' failed: {}", plugin, source),
+            SsgError::RouteData {
+                route,
+                pattern,
+                source,
+            } => write!(
+                f,
+                "Loading the data of route '{}' (pattern {}) failed: {}",
+                route, pattern, source
+            ),
+            SsgError::UnsupportedKey { component, key } => {
+                write!(f, "{} does not support key: {}", component, key)
+            }
+            SsgError::Io { path, source } => write!(f, "IO error at {:?}: {}", path, source),
+            SsgError::ConfigParse {
+                file,
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "Failed to parse configuration")?;
+                if let Some(file) = file {
+                    write!(f, " {:?}", file)?;
+                }
+                match (line, column) {
+                    (Some(line), Some(column)) => {
+                        write!(f, " at line {}, column {}", line, column)?
+                    }
+                    (Some(line), None) => write!(f, " at line {}", line)?,
+                    _ => {}
+                }
+                write!(f, ": {}", message)
+            }
+            SsgError::Config(message) => write!(f, "{}", message),
+            SsgError::RouteRecognition {
+                path,
+                pattern: Some(pattern),
+            } => write!(
+                f,
+                "No route recognized for path {} (pattern {})",
+                path, pattern
+            ),
+            SsgError::RouteRecognition {
+                path,
+                pattern: None,
+            } => write!(f, "No route recognized for path {}", path),
+            SsgError::OutputCollision {
+                route,
+                output_file,
+                existing_route,
+            } => write!(
+                f,
+                "Route '{}' would overwrite {:?}, already written for route '{}' in this run",
+                route, output_file, existing_route
+            ),
+            SsgError::PagesFailed { report } => {
+                let attempted = report.pages.len() + report.failures.len();
+                write!(
+                    f,
+                    "{} of {} routes failed",
+                    report.failures.len(),
+                    attempted
+                )?;
+                for failure in &report.failures {
+                    write!(f, "\n  {}", failure)?;
+                }
+                Ok(())
+            }
+            SsgError::BrokenLinks { report } => {
+                write!(f, "{} broken links", report.broken.len())?;
+                for broken in &report.broken {
+                    write!(f, "\n  {}", broken)?;
+                }
+                Ok(())
+            }
+            SsgError::Custom(source) => write!(f, "{}", source),
+        }
+    }
+}
+
+impl Error for SsgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SsgError::TemplateLoad { source, .. }
+            | SsgError::Generator { source, .. }
+            | SsgError::Processor { source, .. }
+            | SsgError::Plugin { source, .. }
+            | SsgError::RouteData { source, .. } => Some(source.as_ref()),
+            SsgError::TemplateRender { source, .. } => Some(source),
+            SsgError::Io { source, .. } => Some(source),
+            SsgError::Custom(source) => source.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for SsgError {
+    fn from(message: String) -> Self {
+        SsgError::custom(message)
+    }
+}
+
+impl From<&str> for SsgError {
+    fn from(message: &str) -> Self {
+        SsgError::custom(message)
+    }
+}
+
+impl From<serde_json::Error> for SsgError {
+    fn from(error: serde_json::Error) -> Self {
+        SsgError::custom(error)
+    }
+}
+
+/// Attach the path to IO errors
+pub(crate) trait IoContext<T> {
+    fn with_path(self, path: &Path) -> Result<T>;
+}
+
+impl<T> IoContext<T> for io::Result<T> {
+    fn with_path(self, path: &Path) -> Result<T> {
+        self.map_err(|source| SsgError::io(path, source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::generator::Generator;
+    use crate::redirects::Redirect;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_generator_context() {
+        let error = SsgError::custom("boom").in_generator("/about", "json_ld");
+        assert_eq!(error.route(), Some("/about"));
+        assert_eq!(error.component(), Some("json_ld"));
+        assert_eq!(
+            error.to_string(),
+            "Generator 'json_ld' failed for route '/about': boom"
+        );
+
+        // Context is only attached once
+        let error = error.in_processor("/about", "attribute");
+        assert!(matches!(error, SsgError::Generator { .. }));
+    }
+
+    #[test]
+    fn test_config_parse_display() {
+        let error = SsgError::ConfigParse {
+            file: Some(PathBuf::from("config.yaml")),
+            line: Some(3),
+            column: Some(7),
+            message: "invalid type".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse configuration \"config.yaml\" at line 3, column 7: invalid type"
+        );
+    }
+
+    #[derive(Debug, Clone)]
+    struct FailingGenerator;
+
+    impl Generator for FailingGenerator {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn generate(
+            &self,
+            _key: &str,
+            _route: &str,
+            _content: &str,
+            _metadata: &HashMap<String, String>,
+        ) -> Result<String, SsgError> {
+            let error = std::io::Error::other("backend unavailable");
+            Err(SsgError::custom(error))
+        }
+
+        fn clone_box(&self) -> Box<dyn Generator> {
+            Box::new(self.clone())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generator_error_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .add_generator(FailingGenerator)
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let err = generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SsgError::Generator { .. }));
+        assert_eq!(err.component(), Some("failing"));
+        assert!(err.route().is_some());
+        assert!(err.to_string().contains("backend unavailable"));
+
+        // Collisions are reported as typed errors as well
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .redirect(Redirect::new("/", "/crate/a"))
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let err = generator.generate_redirects().unwrap_err();
+        assert!(matches!(err, SsgError::OutputCollision { .. }));
+    }
+}
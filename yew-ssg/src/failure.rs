@@ -0,0 +1,181 @@
+//! Handling of routes that fail to generate.
+//!
+//! By default the first failing route aborts the build. With
+//! [`FailurePolicy::ContinueAndReport`] or [`FailurePolicy::SkipRoute`] every route is
+//! attempted and the failures are collected as [`PageFailure`]s in the generation report.
+
+use crate::error::SsgError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What to do when a route fails to generate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Stop at the first failing route and return its error
+    #[default]
+    FailFast,
+    /// Generate all other routes, then return [`SsgError::PagesFailed`] listing the failures
+    ContinueAndReport,
+    /// Generate all other routes and succeed; failures are only listed in the report
+    SkipRoute,
+}
+
+/// Step of the page pipeline in which a route failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildStage {
+    /// Loading the route data
+    Data,
+    /// Server-side rendering of the component tree
+    Render,
+    /// A generator
+    Generator,
+    /// A processor
+    Processor,
+    /// Loading or rendering the page template
+    Template,
+    /// Writing the output files
+    Write,
+    /// A build plugin
+    Plugin,
+    /// Any other step
+    Other,
+}
+
+impl BuildStage {
+    /// Stage an error was raised in
+    pub fn of(error: &SsgError) -> Self {
+        match error {
+            SsgError::RouteData { .. } => BuildStage::Data,
+            SsgError::ComponentPanic { .. } => BuildStage::Render,
+            SsgError::Generator { .. } => BuildStage::Generator,
+            SsgError::Processor { .. } => BuildStage::Processor,
+            SsgError::TemplateLoad { .. } | SsgError::TemplateRender { .. } => BuildStage::Template,
+            SsgError::Io { .. } => BuildStage::Write,
+            SsgError::Plugin { .. } => BuildStage::Plugin,
+            _ => BuildStage::Other,
+        }
+    }
+}
+
+impl fmt::Display for BuildStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BuildStage::Data => "data",
+            BuildStage::Render => "render",
+            BuildStage::Generator => "generator",
+            BuildStage::Processor => "processor",
+            BuildStage::Template => "template",
+            BuildStage::Write => "write",
+            BuildStage::Plugin => "plugin",
+            BuildStage::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A route that could not be generated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageFailure {
+    /// Route path of the page (including any path prefix)
+    pub route: String,
+    /// Step that failed
+    pub stage: BuildStage,
+    /// Generator or processor that failed, if any
+    pub component: Option<String>,
+    /// Error message
+    pub message: String,
+}
+
+impl PageFailure {
+    /// Record the failure of a route
+    pub fn new(route: &str, error: &SsgError) -> Self {
+        Self {
+            route: route.to_string(),
+            stage: BuildStage::of(error),
+            component: error.component().map(str::to_string),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PageFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.route, self.stage, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::ContextRoute;
+    use yew::prelude::*;
+
+    #[test]
+    fn test_page_failure_from_error() {
+        let error = SsgError::custom("boom").in_processor("/about", "attribute");
+        let failure = PageFailure::new("/about", &error);
+
+        assert_eq!(failure.stage, BuildStage::Processor);
+        assert_eq!(failure.component.as_deref(), Some("attribute"));
+        assert_eq!(
+            failure.to_string(),
+            "/about (processor): Processor 'attribute' failed for route '/about': boom"
+        );
+
+        let policy: FailurePolicy = serde_json::from_str("\"continue_and_report\"").unwrap();
+        assert_eq!(policy, FailurePolicy::ContinueAndReport);
+    }
+
+    #[function_component(PanicProbe)]
+    fn panic_probe() -> Html {
+        let path = yew_router::use_static_path().unwrap_or_default();
+        if path == "/crate/b" {
+            panic!("cannot render {}", path);
+        }
+        html! { <p>{path}</p> }
+    }
+
+    #[tokio::test]
+    async fn test_failure_policies() {
+        let run = |policy: FailurePolicy| async move {
+            let dir = tempfile::tempdir().unwrap();
+            let config = SsgConfigBuilder::new()
+                .output_dir(dir.path())
+                .add_route_param("/crate/:id", "id", ["a", "b", "c"])
+                .failure_policy(policy)
+                .build();
+            let generator = StaticSiteGenerator::new(config).unwrap();
+            let result = generator
+                .generate_parameterized_routes::<ContextRoute, PanicProbe>()
+                .await;
+            (dir, result)
+        };
+
+        let (_dir, result) = run(FailurePolicy::FailFast).await;
+        let err = result.unwrap_err();
+        assert!(matches!(err, SsgError::ComponentPanic { .. }));
+        assert_eq!(err.route(), Some("/crate/b"));
+
+        // Every other route is still written before the build reports the failure
+        let (dir, result) = run(FailurePolicy::ContinueAndReport).await;
+        let err = result.unwrap_err();
+        assert!(err.to_string().starts_with("1 of 3 routes failed"));
+        let report = err.into_report().unwrap();
+        assert_eq!(report.pages.len(), 2);
+        assert_eq!(report.failures[0].route, "/crate/b");
+        assert_eq!(report.failures[0].stage, BuildStage::Render);
+        assert!(dir.path().join("crate/a/index.html").exists());
+        assert!(dir.path().join("crate/c/index.html").exists());
+        assert!(!dir.path().join("crate/b/index.html").exists());
+
+        let (_dir, result) = run(FailurePolicy::SkipRoute).await;
+        let report = result.unwrap();
+        assert_eq!(report.pages.len(), 2);
+        assert!(!report.is_success());
+        assert!(report.into_result().is_err());
+    }
+}
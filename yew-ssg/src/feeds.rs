@@ -0,0 +1,644 @@
+//! RSS 2.0, Atom and JSON Feed generation.
+//!
+//! Feeds are configured per route pattern (`/blog/:id`) or route prefix (`/blog/`).
+//! Every generated page matching a feed becomes an item, built from the same metadata
+//! the `JsonLdGenerator` reads:
+//! - `title`, `description`
+//! - `date_published` (and `date_modified` for Atom's `<updated>`)
+//! - `author`
+//!
+//! Dates are `YYYY-MM-DD` or RFC 3339 timestamps and are interpreted as UTC.
+//! With `per_language` enabled a separate feed is written for every page language.
+
+use crate::paths::{match_pattern, strip_lang_prefix};
+use crate::sitemap::escape_xml;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Output format of a feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedFormat {
+    /// RSS 2.0, written to `rss.xml`
+    Rss,
+    /// Atom 1.0, written to `atom.xml`
+    Atom,
+    /// JSON Feed 1.1, written to `feed.json`
+    Json,
+}
+
+impl FeedFormat {
+    /// File name of the feed inside its output directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "rss.xml",
+            FeedFormat::Atom => "atom.xml",
+            FeedFormat::Json => "feed.json",
+        }
+    }
+
+    /// MIME type used in `<link rel="alternate">` tags
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml",
+            FeedFormat::Atom => "application/atom+xml",
+            FeedFormat::Json => "application/feed+json",
+        }
+    }
+}
+
+/// Configuration of a single feed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedConfig {
+    /// Feed title
+    pub title: String,
+
+    /// Feed description
+    #[serde(default)]
+    pub description: String,
+
+    /// Route pattern whose pages become items (e.g. `/blog/:id`)
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Route prefix whose pages become items (e.g. `/blog/`)
+    #[serde(default)]
+    pub prefix: Option<String>,
+
+    /// Directory the feed files are written to, relative to the output directory
+    #[serde(default)]
+    pub output_dir: String,
+
+    /// Formats to write
+    #[serde(default = "default_formats")]
+    pub formats: Vec<FeedFormat>,
+
+    /// Write one feed per page language (non-default languages below `/<lang>/`)
+    #[serde(default)]
+    pub per_language: bool,
+
+    /// Maximum number of items, newest first
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl FeedConfig {
+    /// Create an RSS feed for all pages matching a route pattern
+    pub fn for_pattern(title: &str, pattern: &str) -> Self {
+        Self {
+            pattern: Some(pattern.to_string()),
+            ..Self::new(title)
+        }
+    }
+
+    /// Create an RSS feed for all pages below a route prefix
+    pub fn for_prefix(title: &str, prefix: &str) -> Self {
+        Self {
+            prefix: Some(prefix.to_string()),
+            ..Self::new(title)
+        }
+    }
+
+    fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            description: String::new(),
+            pattern: None,
+            prefix: None,
+            output_dir: String::new(),
+            formats: default_formats(),
+            per_language: false,
+            limit: None,
+        }
+    }
+
+    /// Whether a route path belongs to this feed.
+    ///
+    /// Pages of language `lang` also match below their language prefix, so `/blog/:id` covers
+    /// `/de/blog/x` as well.
+    pub fn matches(&self, route_path: &str, lang: Option<&str>) -> bool {
+        let matches = |path: &str| {
+            let by_pattern = self
+                .pattern
+                .as_deref()
+                .is_some_and(|pattern| match_pattern(pattern, path).is_some());
+            let by_prefix = self
+                .prefix
+                .as_deref()
+                .is_some_and(|prefix| path.starts_with(prefix) && path != prefix);
+            by_pattern || by_prefix
+        };
+        matches(route_path) || strip_lang_prefix(route_path, lang).is_some_and(matches)
+    }
+
+    /// Output path of a feed file relative to the output directory.
+    ///
+    /// `lang` is the language directory for per-language feeds, `None` for the default language.
+    pub fn file_path(&self, format: FeedFormat, lang: Option<&str>) -> String {
+        let mut segments = Vec::new();
+        if let Some(lang) = lang.filter(|_| self.per_language) {
+            segments.push(lang);
+        }
+        let output_dir = self.output_dir.trim_matches('/');
+        if !output_dir.is_empty() {
+            segments.push(output_dir);
+        }
+        segments.push(format.file_name());
+        segments.join("/")
+    }
+}
+
+/// A feed item built from page metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedItem {
+    /// Absolute URL of the page
+    pub url: String,
+    /// Page title
+    pub title: String,
+    /// Page description
+    pub description: Option<String>,
+    /// Publication date as given in the metadata
+    pub date_published: Option<String>,
+    /// Modification date as given in the metadata
+    pub date_modified: Option<String>,
+    /// Author name
+    pub author: Option<String>,
+}
+
+impl FeedItem {
+    /// Build an item for the page at `url` from its metadata
+    pub fn from_metadata(url: String, metadata: &HashMap<String, String>) -> Self {
+        Self {
+            title: metadata
+                .get("title")
+                .cloned()
+                .unwrap_or_else(|| url.clone()),
+            url,
+            description: metadata.get("description").cloned(),
+            date_published: metadata.get("date_published").cloned(),
+            date_modified: metadata.get("date_modified").cloned(),
+            author: metadata.get("author").cloned(),
+        }
+    }
+
+    fn updated(&self) -> Option<&str> {
+        self.date_modified
+            .as_deref()
+            .or(self.date_published.as_deref())
+    }
+}
+
+/// A feed ready to be rendered in any [`FeedFormat`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// Feed title
+    pub title: String,
+    /// Feed description
+    pub description: String,
+    /// Absolute URL of the site (or language root)
+    pub home_page_url: String,
+    /// Language of the items, if known
+    pub language: Option<String>,
+    /// Items, newest first
+    pub items: Vec<FeedItem>,
+}
+
+impl Feed {
+    /// Create a feed, sorting the items newest first and applying the configured limit
+    pub fn new(
+        config: &FeedConfig,
+        home_page_url: String,
+        language: Option<String>,
+        mut items: Vec<FeedItem>,
+    ) -> Self {
+        items.sort_by(|a, b| {
+            b.date_published
+                .cmp(&a.date_published)
+                .then_with(|| a.url.cmp(&b.url))
+        });
+        if let Some(limit) = config.limit {
+            items.truncate(limit);
+        }
+        Self {
+            title: config.title.clone(),
+            description: config.description.clone(),
+            home_page_url,
+            language,
+            items,
+        }
+    }
+
+    /// Render the feed; `feed_url` is the absolute URL of the rendered file
+    pub fn render(&self, format: FeedFormat, feed_url: &str) -> String {
+        match format {
+            FeedFormat::Rss => self.render_rss(feed_url),
+            FeedFormat::Atom => self.render_atom(feed_url),
+            FeedFormat::Json => self.render_json(feed_url),
+        }
+    }
+
+    fn render_rss(&self, feed_url: &str) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+             xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n",
+        );
+        xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&self.title)));
+        xml.push_str(&format!(
+            "  <link>{}</link>\n",
+            escape_xml(&self.home_page_url)
+        ));
+        xml.push_str(&format!(
+            "  <description>{}</description>\n",
+            escape_xml(&self.description)
+        ));
+        xml.push_str(&format!(
+            "  <atom:link href=\"{}\" rel=\"self\" type=\"{}\"/>\n",
+            escape_xml(feed_url),
+            FeedFormat::Rss.mime_type()
+        ));
+        if let Some(language) = &self.language {
+            xml.push_str(&format!(
+                "  <language>{}</language>\n",
+                escape_xml(language)
+            ));
+        }
+        if let Some(date) = self
+            .items
+            .first()
+            .and_then(|item| item.updated())
+            .and_then(rfc822)
+        {
+            xml.push_str(&format!("  <lastBuildDate>{}</lastBuildDate>\n", date));
+        }
+
+        for item in &self.items {
+            xml.push_str("  <item>\n");
+            xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&item.title)));
+            xml.push_str(&format!("    <link>{}</link>\n", escape_xml(&item.url)));
+            xml.push_str(&format!(
+                "    <guid isPermaLink=\"true\">{}</guid>\n",
+                escape_xml(&item.url)
+            ));
+            if let Some(description) = &item.description {
+                xml.push_str(&format!(
+                    "    <description>{}</description>\n",
+                    escape_xml(description)
+                ));
+            }
+            if let Some(date) = item.date_published.as_deref().and_then(rfc822) {
+                xml.push_str(&format!("    <pubDate>{}</pubDate>\n", date));
+            }
+            if let Some(author) = &item.author {
+                // RSS <author> has to be an email address, so names go into dc:creator
+                xml.push_str(&format!(
+                    "    <dc:creator>{}</dc:creator>\n",
+                    escape_xml(author)
+                ));
+            }
+            xml.push_str("  </item>\n");
+        }
+
+        xml.push_str("</channel>\n</rss>\n");
+        xml
+    }
+
+    fn render_atom(&self, feed_url: &str) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\"",
+        );
+        if let Some(language) = &self.language {
+            xml.push_str(&format!(" xml:lang=\"{}\"", escape_xml(language)));
+        }
+        xml.push_str(">\n");
+        xml.push_str(&format!("  <id>{}</id>\n", escape_xml(feed_url)));
+        xml.push_str(&format!("  <title>{}</title>\n", escape_xml(&self.title)));
+        if !self.description.is_empty() {
+            xml.push_str(&format!(
+                "  <subtitle>{}</subtitle>\n",
+                escape_xml(&self.description)
+            ));
+        }
+        xml.push_str(&format!(
+            "  <link href=\"{}\"/>\n",
+            escape_xml(&self.home_page_url)
+        ));
+        xml.push_str(&format!(
+            "  <link href=\"{}\" rel=\"self\"/>\n",
+            escape_xml(feed_url)
+        ));
+        let updated = self
+            .items
+            .iter()
+            .filter_map(|item| item.updated().and_then(rfc3339))
+            .max()
+            .unwrap_or_else(|| "1970-01-01T00:00:00Z".to_string());
+        xml.push_str(&format!("  <updated>{}</updated>\n", updated));
+
+        for item in &self.items {
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&item.url)));
+            xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&item.title)));
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(&item.url)));
+            let entry_updated = item
+                .updated()
+                .and_then(rfc3339)
+                .unwrap_or_else(|| updated.clone());
+            xml.push_str(&format!("    <updated>{}</updated>\n", entry_updated));
+            if let Some(date) = item.date_published.as_deref().and_then(rfc3339) {
+                xml.push_str(&format!("    <published>{}</published>\n", date));
+            }
+            if let Some(author) = &item.author {
+                xml.push_str(&format!(
+                    "    <author><name>{}</name></author>\n",
+                    escape_xml(author)
+                ));
+            }
+            if let Some(description) = &item.description {
+                xml.push_str(&format!(
+                    "    <summary>{}</summary>\n",
+                    escape_xml(description)
+                ));
+            }
+            xml.push_str("  </entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    fn render_json(&self, feed_url: &str) -> String {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|item| {
+                let mut value = json!({
+                    "id": item.url,
+                    "url": item.url,
+                    "title": item.title,
+                });
+                if let Some(description) = &item.description {
+                    value["summary"] = json!(description);
+                    value["content_text"] = json!(description);
+                } else {
+                    value["content_text"] = json!("");
+                }
+                if let Some(date) = item.date_published.as_deref().and_then(rfc3339) {
+                    value["date_published"] = json!(date);
+                }
+                if let Some(date) = item.date_modified.as_deref().and_then(rfc3339) {
+                    value["date_modified"] = json!(date);
+                }
+                if let Some(author) = &item.author {
+                    value["authors"] = json!([{ "name": author }]);
+                }
+                value
+            })
+            .collect();
+
+        let mut feed = json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": self.title,
+            "home_page_url": self.home_page_url,
+            "feed_url": feed_url,
+            "items": items,
+        });
+        if !self.description.is_empty() {
+            feed["description"] = json!(self.description);
+        }
+        if let Some(language) = &self.language {
+            feed["language"] = json!(language);
+        }
+        serde_json::to_string_pretty(&feed).unwrap_or_default()
+    }
+}
+
+fn default_formats() -> Vec<FeedFormat> {
+    vec![FeedFormat::Rss]
+}
+
+/// Split a `YYYY-MM-DD[THH:MM:SS...]` date into its date parts and time
+fn parse_date(value: &str) -> Option<(i64, u32, u32, &str)> {
+    let date = value.get(..10)?;
+    let mut parts = date.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let time = value
+        .get(11..19)
+        .filter(|time| time.len() == 8 && time.as_bytes()[2] == b':')
+        .unwrap_or("00:00:00");
+    Some((year, month, day, time))
+}
+
+/// Format a date as RFC 822 (RSS)
+fn rfc822(value: &str) -> Option<String> {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let (year, month, day, time) = parse_date(value)?;
+    // Sakamoto's day-of-week algorithm
+    const OFFSETS: [i64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let weekday = (y + y / 4 - y / 100 + y / 400 + OFFSETS[month as usize - 1] + day as i64)
+        .rem_euclid(7) as usize;
+
+    Some(format!(
+        "{}, {:02} {} {} {} +0000",
+        DAYS[weekday],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        time
+    ))
+}
+
+/// Format a date as RFC 3339 (Atom, JSON Feed)
+fn rfc3339(value: &str) -> Option<String> {
+    let (year, month, day, time) = parse_date(value)?;
+    Some(format!("{:04}-{:02}-{:02}T{}Z", year, month, day, time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use std::fs;
+    use yew_router::Routable;
+
+    fn item(path: &str, title: &str, date: &str) -> FeedItem {
+        FeedItem::from_metadata(
+            format!("https://example.com{}", path),
+            &HashMap::from([
+                ("title".to_string(), title.to_string()),
+                ("description".to_string(), format!("About {}", title)),
+                ("date_published".to_string(), date.to_string()),
+                ("author".to_string(), "Jane & John".to_string()),
+            ]),
+        )
+    }
+
+    fn feed() -> Feed {
+        let mut config = FeedConfig::for_pattern("Blog", "/blog/:id");
+        config.description = "Posts".to_string();
+        config.limit = Some(2);
+        Feed::new(
+            &config,
+            "https://example.com/".to_string(),
+            Some("en".to_string()),
+            vec![
+                item("/blog/old", "Old", "2024-01-01"),
+                item("/blog/new", "New", "2025-02-15"),
+                item("/blog/mid", "Mid", "2024-06-30T12:30:00Z"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_feed_config_matching_and_paths() {
+        let mut config = FeedConfig::for_pattern("Blog", "/blog/:id");
+        assert!(config.matches("/blog/first-post", None));
+        assert!(!config.matches("/blog/", None));
+        assert!(!config.matches("/about", None));
+        assert!(config.matches("/de/blog/erster", Some("de")));
+        assert!(!config.matches("/de/blog/erster", None));
+
+        let prefix = FeedConfig::for_prefix("Docs", "/docs/");
+        assert!(prefix.matches("/docs/intro/setup", None));
+        assert!(!prefix.matches("/docs/", None));
+        assert!(prefix.matches("/fr/docs/intro", Some("fr")));
+
+        config.output_dir = "/blog/".to_string();
+        assert_eq!(
+            config.file_path(FeedFormat::Rss, Some("de")),
+            "blog/rss.xml"
+        );
+        config.per_language = true;
+        assert_eq!(
+            config.file_path(FeedFormat::Json, Some("de")),
+            "de/blog/feed.json"
+        );
+        assert_eq!(config.file_path(FeedFormat::Atom, None), "blog/atom.xml");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(
+            rfc822("2025-02-15").unwrap(),
+            "Sat, 15 Feb 2025 00:00:00 +0000"
+        );
+        assert_eq!(
+            rfc822("2024-06-30T12:30:00Z").unwrap(),
+            "Sun, 30 Jun 2024 12:30:00 +0000"
+        );
+        assert_eq!(rfc3339("2025-02-15").unwrap(), "2025-02-15T00:00:00Z");
+        assert!(rfc3339("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_render_rss() {
+        let rss = feed().render(FeedFormat::Rss, "https://example.com/rss.xml");
+        assert!(rss.contains("<title>Blog</title>"));
+        assert!(rss.contains("<language>en</language>"));
+        assert!(rss.contains("<pubDate>Sat, 15 Feb 2025 00:00:00 +0000</pubDate>"));
+        assert!(rss.contains("<link>https://example.com/blog/new</link>"));
+        assert!(rss.contains("<dc:creator>Jane &amp; John</dc:creator>"));
+        assert!(!rss.contains("/blog/old"));
+        assert!(rss.find("/blog/new").unwrap() < rss.find("/blog/mid").unwrap());
+    }
+
+    #[test]
+    fn test_render_atom() {
+        let atom = feed().render(FeedFormat::Atom, "https://example.com/atom.xml");
+        assert!(atom.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"en\">"));
+        assert!(atom.contains("<updated>2025-02-15T00:00:00Z</updated>"));
+        assert!(atom.contains("<author><name>Jane &amp; John</name></author>"));
+        assert!(atom.contains("<published>2024-06-30T12:30:00Z</published>"));
+    }
+
+    #[test]
+    fn test_render_json_feed() {
+        let json = feed().render(FeedFormat::Json, "https://example.com/feed.json");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(value["feed_url"], "https://example.com/feed.json");
+        assert_eq!(value["items"].as_array().unwrap().len(), 2);
+        assert_eq!(value["items"][0]["title"], "New");
+        assert_eq!(value["items"][0]["authors"][0]["name"], "Jane & John");
+        assert_eq!(value["items"][0]["date_published"], "2025-02-15T00:00:00Z");
+    }
+
+    #[derive(Clone, Routable, PartialEq, Debug, strum_macros::EnumIter)]
+    enum BlogRoute {
+        #[at("/")]
+        Home,
+        #[at("/blog/:id")]
+        Post { id: String },
+        #[at("/de/blog/:id")]
+        PostDe { id: String },
+    }
+
+    #[test]
+    fn test_generate_feeds() {
+        let mut feed = FeedConfig::for_pattern("Blog", "/blog/:id");
+        feed.formats = vec![FeedFormat::Rss, FeedFormat::Json];
+        feed.per_language = true;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com".to_string(),
+            )]))
+            .route_metadata(
+                "/de/",
+                HashMap::from([("lang".to_string(), "de".to_string())]),
+            )
+            .add_route_param("/blog/:id", "id", ["first", "second"])
+            .add_param_metadata(
+                "/blog/:id",
+                "id",
+                "second",
+                HashMap::from([
+                    ("title".to_string(), "Second".to_string()),
+                    ("date_published".to_string(), "2025-02-15".to_string()),
+                ]),
+            )
+            .add_route_param("/de/blog/:id", "id", ["erster"])
+            .feed(feed)
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        // One feed config covers the German pages below `/de/` as well
+        let written = generator.generate_feeds::<BlogRoute>().unwrap();
+        assert_eq!(written.len(), 4);
+
+        let rss = fs::read_to_string(dir.path().join("rss.xml")).unwrap();
+        assert!(rss.contains("<link>https://example.com/blog/second</link>"));
+        assert!(rss.contains("<link>https://example.com/blog/first</link>"));
+        assert!(!rss.contains("erster"));
+        assert!(rss.find("/blog/second").unwrap() < rss.find("/blog/first").unwrap());
+        assert!(dir.path().join("feed.json").exists());
+
+        let rss_de = fs::read_to_string(dir.path().join("de/rss.xml")).unwrap();
+        assert!(rss_de.contains("<link>https://example.com/de/blog/erster</link>"));
+        assert!(rss_de.contains("<language>de</language>"));
+        assert!(!rss_de.contains("/blog/first"));
+        assert!(dir.path().join("de/feed.json").exists());
+
+        let feed_links = generator
+            .run_generators("/", "", &generator.config.get_metadata_for_route("/"))
+            .unwrap()
+            .remove("feed_links")
+            .unwrap();
+        assert!(feed_links.contains(r#"href="https://example.com/rss.xml""#));
+    }
+}
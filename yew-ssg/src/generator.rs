@@ -1,6 +1,6 @@
+use crate::error::SsgError;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 use std::fmt::Debug;
 
 pub trait Generator: Debug + Send + Sync {
@@ -25,7 +25,7 @@ pub trait Generator: Debug + Send + Sync {
         route: &str,
         content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>>;
+    ) -> Result<String, SsgError>;
 
     /// Creates a boxed clone of this generator
     fn clone_box(&self) -> Box<dyn Generator>;
@@ -72,7 +72,7 @@ pub mod tests {
             _route: &str,
             _content: &str,
             _metadata: &HashMap<String, String>,
-        ) -> Result<String, Box<dyn Error>> {
+        ) -> Result<String, SsgError> {
             if key == self.name() || self.supported_keys.contains(&key) {
                 Ok(format!(
                     "<div>Generated content for key '{}' by {}</div>",
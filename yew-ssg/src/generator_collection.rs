@@ -7,6 +7,12 @@ pub struct GeneratorCollection {
     pub(crate) generators: Vec<Box<dyn Generator>>,
 }
 
+impl Default for GeneratorCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GeneratorCollection {
     pub fn new() -> Self {
         Self {
@@ -21,7 +27,7 @@ impl GeneratorCollection {
     /// Try to extract GeneratorOutputSupport from a generator
     pub fn try_get_output_support<'a>(
         &self,
-        generator: &'a Box<dyn Generator>,
+        generator: &'a dyn Generator,
     ) -> Option<&'a dyn GeneratorOutputSupport> {
         use crate::generators::{
             MetaTagGenerator, OpenGraphGenerator, RobotsMetaGenerator, TitleGenerator,
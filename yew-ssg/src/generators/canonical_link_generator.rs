@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 
 /// Generator for canonical URLs and alternate language links
 ///
@@ -69,7 +69,7 @@ impl CanonicalLinkGenerator {
     }
 
     /// Remove any known language prefix from a path (for canonical and alternates)
-    fn strip_any_language_prefix<'a>(&self, path: &'a str, langs: &[&str]) -> String {
+    fn strip_any_language_prefix(&self, path: &str, langs: &[&str]) -> String {
         for lang in langs {
             let prefix = format!("/{}/", lang);
             let prefix_end = format!("/{}", lang);
@@ -154,7 +154,23 @@ impl CanonicalLinkGenerator {
 
     /// Generate alternate language links
     fn generate_alternate_links(&self, metadata: &HashMap<String, String>) -> String {
-        let mut result = String::new();
+        self.alternate_urls(metadata)
+            .into_iter()
+            .map(|(lang, url)| {
+                format!(
+                    "<link rel=\"alternate\" hreflang=\"{}\" href=\"{}\">\n",
+                    lang, url
+                )
+            })
+            .collect()
+    }
+
+    /// Alternate language URLs of a page as `(hreflang, url)` pairs, ending with `x-default`
+    ///
+    /// These are the same URLs emitted as `<link rel="alternate">` tags, so other outputs
+    /// such as the sitemap can reference exactly the same alternates.
+    pub fn alternate_urls(&self, metadata: &HashMap<String, String>) -> Vec<(String, String)> {
+        let mut result = Vec::new();
         let langs: Vec<&str> = self.all_langs(metadata);
         if langs.is_empty() {
             return result;
@@ -183,10 +199,7 @@ impl CanonicalLinkGenerator {
             }
             let lang_key = format!("alternate_url_{}", lang);
             if let Some(lang_url) = metadata.get(&lang_key) {
-                result.push_str(&format!(
-                    "<link rel=\"alternate\" hreflang=\"{}\" href=\"{}\">\n",
-                    lang, lang_url
-                ));
+                result.push((lang.to_string(), lang_url.clone()));
             } else {
                 let lang_path = if *lang == self.default_language {
                     if clean_base_path.is_empty() {
@@ -210,10 +223,7 @@ impl CanonicalLinkGenerator {
                         .parse()
                         .unwrap()
                 });
-                result.push_str(&format!(
-                    "<link rel=\"alternate\" hreflang=\"{}\" href=\"{}\">\n",
-                    lang, full_url
-                ));
+                result.push((lang.to_string(), full_url.to_string()));
             }
         }
         // x-default
@@ -240,13 +250,15 @@ impl CanonicalLinkGenerator {
                 })
                 .to_string()
         };
-        result.push_str(&format!(
-            "<link rel=\"alternate\" hreflang=\"x-default\" href=\"{}\">\n",
-            x_default_url
-        ));
+        result.push(("x-default".to_string(), x_default_url));
         result
     }
 
+    /// Canonical URL of a page, or `None` when neither a `canonical` override nor a domain is known
+    pub fn canonical_url(&self, metadata: &HashMap<String, String>) -> Option<String> {
+        Some(self.get_canonical_url(metadata)).filter(|url| !url.is_empty())
+    }
+
     /// Get just the canonical URL value (without HTML tags)
     fn get_canonical_url(&self, metadata: &HashMap<String, String>) -> String {
         if let Some(canonical) = metadata.get("canonical") {
@@ -311,7 +323,7 @@ impl Generator for CanonicalLinkGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             // Main output: all link tags (canonical + alternates)
             "canonical_links" => {
@@ -342,7 +354,10 @@ impl Generator for CanonicalLinkGenerator {
             "alternate_links" => Ok(self.generate_alternate_links(metadata)),
 
             // Unsupported key
-            _ => Err(format!("CanonicalLinkGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "CanonicalLinkGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
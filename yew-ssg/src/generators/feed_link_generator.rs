@@ -0,0 +1,115 @@
+use crate::error::SsgError;
+use crate::feeds::FeedConfig;
+use crate::generator::Generator;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Generator for `<link rel="alternate">` tags announcing the site's feeds
+///
+/// Pages of a non-default language link to their language's feed when the feed
+/// is configured with `per_language`.
+#[derive(Debug, Clone)]
+pub struct FeedLinkGenerator {
+    /// Feeds to announce
+    pub feeds: Vec<FeedConfig>,
+
+    /// Language whose feeds live at the top level
+    pub default_language: String,
+}
+
+impl FeedLinkGenerator {
+    /// Create a generator announcing the given feeds
+    pub fn new(feeds: Vec<FeedConfig>) -> Self {
+        Self {
+            feeds,
+            default_language: "en".to_string(),
+        }
+    }
+}
+
+impl Generator for FeedLinkGenerator {
+    fn name(&self) -> &'static str {
+        "feed_links"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn generate(
+        &self,
+        key: &str,
+        _route: &str,
+        _content: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Result<String, SsgError> {
+        if key != "feed_links" {
+            return Err(SsgError::UnsupportedKey {
+                component: "FeedLinkGenerator".to_string(),
+                key: key.to_string(),
+            });
+        }
+
+        let lang = metadata
+            .get("lang")
+            .filter(|lang| **lang != self.default_language)
+            .map(String::as_str);
+        let domain = metadata
+            .get("domain")
+            .map(|domain| domain.trim_end_matches('/'))
+            .unwrap_or("");
+
+        let mut result = String::new();
+        for feed in &self.feeds {
+            for format in &feed.formats {
+                result.push_str(&format!(
+                    "<link rel=\"alternate\" type=\"{}\" title=\"{}\" href=\"{}/{}\">\n",
+                    format.mime_type(),
+                    feed.title.replace('"', "&quot;"),
+                    domain,
+                    feed.file_path(*format, lang)
+                ));
+            }
+        }
+        Ok(result)
+    }
+
+    fn clone_box(&self) -> Box<dyn Generator> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feeds::FeedFormat;
+
+    #[test]
+    fn test_feed_links() {
+        let mut blog = FeedConfig::for_pattern("Blog", "/blog/:id");
+        blog.output_dir = "blog".to_string();
+        blog.formats = vec![FeedFormat::Rss, FeedFormat::Json];
+        blog.per_language = true;
+        let generator = FeedLinkGenerator::new(vec![blog]);
+
+        let metadata = HashMap::from([
+            ("domain".to_string(), "https://example.com/".to_string()),
+            ("lang".to_string(), "en".to_string()),
+        ]);
+        let result = generator
+            .generate("feed_links", "/", "", &metadata)
+            .unwrap();
+        assert!(result.contains(
+            r#"<link rel="alternate" type="application/rss+xml" title="Blog" href="https://example.com/blog/rss.xml">"#
+        ));
+        assert!(result.contains(r#"type="application/feed+json""#));
+
+        let metadata = HashMap::from([("lang".to_string(), "de".to_string())]);
+        let result = generator
+            .generate("feed_links", "/de/", "", &metadata)
+            .unwrap();
+        assert!(result.contains(r#"href="/de/blog/rss.xml""#));
+
+        assert!(generator.generate("other", "/", "", &metadata).is_err());
+    }
+}
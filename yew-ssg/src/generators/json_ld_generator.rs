@@ -1,9 +1,9 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use serde_json::{Value, json};
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 use std::fs;
 use std::path::Path;
 
@@ -59,7 +59,7 @@ impl JsonLdGenerator {
     }
 
     /// Load JSON-LD from a file
-    fn load_json_ld_from_file(&self, file_path: &str) -> Result<Value, Box<dyn Error>> {
+    fn load_json_ld_from_file(&self, file_path: &str) -> Result<Value, SsgError> {
         // Resolve the path (either absolute or relative to base dir)
         let resolved_path = if Path::new(file_path).is_absolute() {
             file_path.to_string()
@@ -70,8 +70,8 @@ impl JsonLdGenerator {
         };
 
         // Read the file content
-        let file_content = fs::read_to_string(&resolved_path)
-            .map_err(|e| format!("Failed to read JSON-LD file '{}': {}", resolved_path, e))?;
+        let file_content =
+            fs::read_to_string(&resolved_path).map_err(|e| SsgError::io(&resolved_path, e))?;
 
         // Parse the JSON content
         let json_value: Value = serde_json::from_str(&file_content)
@@ -192,7 +192,7 @@ impl JsonLdGenerator {
 
     /// Generate BreadcrumbList JSON-LD
     fn generate_breadcrumbs(&self, path: &str, metadata: &HashMap<String, String>) -> Value {
-        let domain = self.get_domain(metadata).unwrap_or_else(|| "".to_string());
+        let domain = self.get_domain(metadata).unwrap_or_default();
 
         // Parse the path into breadcrumb segments
         let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
@@ -221,7 +221,7 @@ impl JsonLdGenerator {
             let name = metadata
                 .get(&key)
                 .cloned()
-                .unwrap_or_else(|| segment.replace('-', " ").replace('_', " "));
+                .unwrap_or_else(|| segment.replace(['-', '_'], " "));
 
             items.push(json!({
                 "@type": "ListItem",
@@ -243,7 +243,7 @@ impl JsonLdGenerator {
         &self,
         route: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<Value, Box<dyn Error>> {
+    ) -> Result<Value, SsgError> {
         // First check if a file path is provided
         if let Some(file_path) = metadata.get("json_ld_file") {
             return self.load_json_ld_from_file(file_path);
@@ -288,12 +288,12 @@ impl Generator for JsonLdGenerator {
         route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         // Check if JSON-LD is explicitly disabled
-        if let Some(enabled) = metadata.get("json_ld_enabled") {
-            if enabled.to_lowercase() == "false" {
-                return Ok("".to_string());
-            }
+        if let Some(enabled) = metadata.get("json_ld_enabled")
+            && enabled.to_lowercase() == "false"
+        {
+            return Ok("".to_string());
         }
 
         match key {
@@ -391,7 +391,10 @@ impl Generator for JsonLdGenerator {
             }
 
             // Unsupported key
-            _ => Err(format!("JsonLdGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "JsonLdGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
@@ -418,6 +421,7 @@ impl GeneratorOutputSupport for JsonLdGenerator {
 mod tests {
     use super::*;
     use std::collections::HashMap;
+    use std::error::Error;
     use std::fs;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct MetaTagGenerator {
@@ -25,7 +25,7 @@ impl Generator for MetaTagGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             // Main output: full meta tags block
             "meta_tags" => {
@@ -111,7 +111,10 @@ impl Generator for MetaTagGenerator {
             }
 
             // Unsupported key
-            _ => Err(format!("MetaTagGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "MetaTagGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
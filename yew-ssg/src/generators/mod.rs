@@ -1,4 +1,5 @@
 mod canonical_link_generator;
+mod feed_link_generator;
 mod json_ld_generator;
 mod meta_tag_generator;
 mod open_graph_generator;
@@ -7,9 +8,11 @@ mod title_generator;
 mod twitter_card_generator;
 
 pub use canonical_link_generator::CanonicalLinkGenerator;
+pub use feed_link_generator::FeedLinkGenerator;
 pub use json_ld_generator::JsonLdGenerator;
 pub use meta_tag_generator::MetaTagGenerator;
 pub use open_graph_generator::OpenGraphGenerator;
 pub use robots_meta_generator::RobotsMetaGenerator;
+pub(crate) use robots_meta_generator::{is_noindex, with_noindex};
 pub use title_generator::TitleGenerator;
 pub use twitter_card_generator::TwitterCardGenerator;
@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -20,10 +20,10 @@ impl OpenGraphGenerator {
         } else if let Some(url) = metadata.get("url") {
             url.clone()
         } else if let (Some(domain), Some(path)) = (metadata.get("domain"), metadata.get("path")) {
-            if let Ok(base) = Url::parse(domain) {
-                if let Ok(joined) = base.join(path) {
-                    return joined.to_string();
-                }
+            if let Ok(base) = Url::parse(domain)
+                && let Ok(joined) = base.join(path)
+            {
+                return joined.to_string();
             }
             // fallback to manual if url crate fails
             let domain = domain.trim_end_matches('/');
@@ -96,7 +96,7 @@ impl Generator for OpenGraphGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             "open_graph" => {
                 let mut tags = String::new();
@@ -256,7 +256,10 @@ impl Generator for OpenGraphGenerator {
                 ))
             }
 
-            _ => Err(format!("OpenGraphGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "OpenGraphGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
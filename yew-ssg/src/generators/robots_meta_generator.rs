@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct RobotsMetaGenerator {
@@ -24,7 +24,7 @@ impl Generator for RobotsMetaGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             // Main output: robots meta tag
             "robots_meta" => {
@@ -47,7 +47,10 @@ impl Generator for RobotsMetaGenerator {
             }
 
             // Unsupported key
-            _ => Err(format!("RobotsMetaGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "RobotsMetaGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
@@ -62,8 +65,43 @@ impl GeneratorOutputSupport for RobotsMetaGenerator {
     }
 }
 
+/// Whether a robots directive list (e.g. `noindex, follow`) keeps a page out of search indexes
+pub(crate) fn is_noindex(robots: &str) -> bool {
+    directives(robots).any(|directive| {
+        directive.eq_ignore_ascii_case("noindex") || directive.eq_ignore_ascii_case("none")
+    })
+}
+
+/// Add `noindex` to a robots directive list, dropping a contradicting `index`
+/// (`index, follow` becomes `noindex, follow`)
+pub(crate) fn with_noindex(robots: &str) -> String {
+    if is_noindex(robots) {
+        return robots.to_string();
+    }
+    let mut directives: Vec<&str> = directives(robots)
+        .filter(|directive| !directive.eq_ignore_ascii_case("index"))
+        .map(|directive| {
+            if directive.eq_ignore_ascii_case("all") {
+                "follow"
+            } else {
+                directive
+            }
+        })
+        .collect();
+    directives.insert(0, "noindex");
+    directives.join(", ")
+}
+
+fn directives(robots: &str) -> impl Iterator<Item = &str> {
+    robots
+        .split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{is_noindex, with_noindex};
     use crate::generator::Generator;
     use crate::generators::RobotsMetaGenerator;
     use std::collections::HashMap;
@@ -136,6 +174,22 @@ mod tests {
         assert_eq!(result, "noindex, nofollow");
     }
 
+    #[test]
+    fn test_noindex_directives() {
+        assert!(is_noindex("noindex, follow"));
+        assert!(is_noindex("NONE"));
+        assert!(!is_noindex("index, follow, max-snippet:-1"));
+
+        assert_eq!(with_noindex(""), "noindex");
+        assert_eq!(with_noindex("index, follow"), "noindex, follow");
+        assert_eq!(with_noindex("all"), "noindex, follow");
+        assert_eq!(
+            with_noindex("nofollow, max-image-preview:large"),
+            "noindex, nofollow, max-image-preview:large"
+        );
+        assert_eq!(with_noindex("noindex, nofollow"), "noindex, nofollow");
+    }
+
     #[test]
     fn test_unsupported_key() {
         let generator = RobotsMetaGenerator {
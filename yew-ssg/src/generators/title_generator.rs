@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct TitleGenerator;
@@ -22,7 +22,7 @@ impl Generator for TitleGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             // Main output: full title tag
             "title" => {
@@ -37,7 +37,10 @@ impl Generator for TitleGenerator {
             }
 
             // Unsupported key
-            _ => Err(format!("TitleGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "TitleGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
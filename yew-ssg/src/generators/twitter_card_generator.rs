@@ -1,8 +1,8 @@
+use crate::error::SsgError;
 use crate::generator::Generator;
 use crate::processors::GeneratorOutputSupport;
 use std::any::Any;
 use std::collections::HashMap;
-use std::error::Error;
 
 /// Generator for Twitter Card meta tags
 ///
@@ -49,7 +49,7 @@ impl Generator for TwitterCardGenerator {
         _route: &str,
         _content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         match key {
             // Main output: full Twitter Card tags
             "twitter_card" => {
@@ -569,7 +569,10 @@ impl Generator for TwitterCardGenerator {
             }
 
             // Unsupported key
-            _ => Err(format!("TwitterCardGenerator does not support key: {}", key).into()),
+            _ => Err(SsgError::UnsupportedKey {
+                component: "TwitterCardGenerator".to_string(),
+                key: key.to_string(),
+            }),
         }
     }
 
@@ -1,25 +1,49 @@
+pub mod assets;
+pub mod build_manifest;
+pub mod build_plan;
 pub mod config;
 pub mod config_loader;
+mod dev_server;
+pub mod error;
+pub mod failure;
+pub mod feeds;
 pub mod generator;
 pub mod generator_collection;
 pub mod generators;
+pub mod link_check;
+mod paths;
+pub mod plugin;
+pub mod precompress;
 pub mod processor;
 pub mod processor_collection;
 pub mod processors;
+pub mod redirects;
+pub mod report;
+pub mod robots_txt;
+pub mod route_data;
+pub mod sitemap;
 pub mod static_site_generator;
+pub mod template_context;
+pub mod template_functions;
+pub mod watch;
 
 pub use config::SsgConfig;
 pub use config::SsgConfigBuilder;
+pub use error::SsgError;
+pub use report::GenerationReport;
 
 pub use static_site_generator::StaticSiteGenerator;
+pub use yew_router::SsgRenderContext;
 
 pub mod prelude {
     // Configuration
     pub use crate::config::{SsgConfig, SsgConfigBuilder};
 
     // Core traits and components
+    pub use crate::error::SsgError;
     pub use crate::generator::Generator;
     pub use crate::generator_collection::GeneratorCollection;
+    pub use crate::plugin::{BuildPlugin, GeneratedPage};
     pub use crate::processor::Processor;
     pub use crate::processor_collection::ProcessorCollection;
 
@@ -30,8 +54,25 @@ pub mod prelude {
     };
 
     // Processor implementations
-    pub use crate::processors::{AttributeProcessor, TemplateVariableProcessor};
+    pub use crate::processors::{
+        AssetProcessor, AttributeProcessor, MinifyProcessor, TemplateVariableProcessor,
+    };
 
     // Static site generator
+    pub use crate::assets::{AssetManifest, AssetPipeline};
+    pub use crate::build_plan::{BuildPlan, PlanIssue, PlannedPage};
+    pub use crate::failure::{BuildStage, FailurePolicy, PageFailure};
+    pub use crate::feeds::{FeedConfig, FeedFormat};
+    pub use crate::link_check::{BrokenLink, LinkCheck, LinkProblem, LinkReport};
+    pub use crate::precompress::{CompressedFile, Precompress};
+    pub use crate::redirects::{Redirect, RedirectHost, Redirects};
+    pub use crate::report::{GenerationReport, PageReport, PageStatus};
+    pub use crate::robots_txt::{RobotsRule, RobotsTxt};
+    pub use crate::route_data::{FileData, RouteData, RouteDataProvider};
+    pub use crate::sitemap::{Sitemap, SitemapUrl};
     pub use crate::static_site_generator::StaticSiteGenerator;
+    pub use crate::template_context::{PageInfo, SiteInfo};
+    pub use crate::template_functions::TemplateFunctions;
+    pub use crate::watch::{Rebuild, SiteWatcher};
+    pub use yew_router::SsgRenderContext;
 }
@@ -0,0 +1,494 @@
+//! Internal link checking of a generated site.
+//!
+//! [`LinkCheck`] reads every HTML page below an output directory and verifies that the
+//! references of `<a>`, `<link>`, `<img>`, `<script>`, ... to the site itself point at existing
+//! files, and that `#fragment` links point at an existing `id`. Pages that no other page links
+//! to are reported as orphans.
+//!
+//! Run it with [`StaticSiteGenerator::check_links`](crate::StaticSiteGenerator::check_links)
+//! once all outputs are written, or standalone on an existing output directory:
+//!
+//! ```no_run
+//! use yew_ssg::link_check::LinkCheck;
+//!
+//! let report = LinkCheck::default().check("dist", Some("https://example.com"))?;
+//! for broken in &report.broken {
+//!     eprintln!("{}", broken);
+//! }
+//! # Ok::<(), yew_ssg::SsgError>(())
+//! ```
+
+use crate::error::{IoContext, SsgError};
+use crate::paths::percent_decode;
+use lol_html::{HtmlRewriter, Settings, element};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Elements and attributes holding references
+const REFERENCES: [(&str, &str); 8] = [
+    ("a[href]", "href"),
+    ("area[href]", "href"),
+    ("link[href]", "href"),
+    ("img[src]", "src"),
+    ("script[src]", "src"),
+    ("source[src]", "src"),
+    ("iframe[src]", "src"),
+    ("video[poster]", "poster"),
+];
+
+/// Settings of the link check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkCheck {
+    /// Fail with [`SsgError::BrokenLinks`] when links are broken
+    #[serde(default)]
+    pub fail_on_broken: bool,
+    /// Report pages no other page links to
+    #[serde(default = "default_orphans")]
+    pub orphans: bool,
+    /// Link prefixes that are not checked (e.g. `/api/`)
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+fn default_orphans() -> bool {
+    true
+}
+
+impl Default for LinkCheck {
+    fn default() -> Self {
+        Self {
+            fail_on_broken: false,
+            orphans: default_orphans(),
+            ignore: Vec::new(),
+        }
+    }
+}
+
+/// Why a link is broken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkProblem {
+    /// No file exists for the link
+    MissingFile,
+    /// The linked page has no element with the `#fragment` id
+    MissingAnchor,
+    /// A relative link climbs above the root of the site (e.g. `../../x` on `blog/index.html`)
+    OutsideSite,
+}
+
+/// A link that does not resolve
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrokenLink {
+    /// Page containing the link, relative to the output directory
+    pub page: PathBuf,
+    /// Line of the link in the page (1-based), if found
+    pub line: Option<usize>,
+    /// Link as written in the page
+    pub href: String,
+    /// Why the link is broken
+    pub problem: LinkProblem,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.page.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        let problem = match self.problem {
+            LinkProblem::MissingFile => "missing file",
+            LinkProblem::MissingAnchor => "missing anchor",
+            LinkProblem::OutsideSite => "outside the site",
+        };
+        write!(f, ": {} ({})", self.href, problem)
+    }
+}
+
+/// Result of a link check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkReport {
+    /// Number of checked pages
+    pub pages: usize,
+    /// Number of checked internal links
+    pub links: usize,
+    /// Links that do not resolve, in page order
+    pub broken: Vec<BrokenLink>,
+    /// Pages no other page links to, relative to the output directory
+    pub orphans: Vec<PathBuf>,
+}
+
+impl LinkReport {
+    /// Whether every link resolves
+    pub fn is_ok(&self) -> bool {
+        self.broken.is_empty()
+    }
+
+    /// The report, or [`SsgError::BrokenLinks`] if links are broken
+    pub fn into_result(self) -> Result<Self, SsgError> {
+        if self.is_ok() {
+            Ok(self)
+        } else {
+            Err(SsgError::BrokenLinks {
+                report: Box::new(self),
+            })
+        }
+    }
+}
+
+/// References and anchors of a page
+#[derive(Debug, Default)]
+struct ScannedPage {
+    /// Links with their line
+    links: Vec<(String, Option<usize>)>,
+    /// Ids and `<a name>` anchors
+    anchors: HashSet<String>,
+    /// The page is a `<meta http-equiv="refresh">` redirect stub
+    redirect: bool,
+}
+
+impl LinkCheck {
+    /// Fail when links are broken
+    pub fn fail_on_broken(mut self, fail: bool) -> Self {
+        self.fail_on_broken = fail;
+        self
+    }
+
+    /// Skip links starting with `prefix`
+    pub fn ignore(mut self, prefix: &str) -> Self {
+        self.ignore.push(prefix.to_string());
+        self
+    }
+
+    /// Check the pages below `output_dir`.
+    ///
+    /// Absolute links starting with `domain` (e.g. canonical URLs) are checked like site
+    /// paths; other absolute URLs are skipped. The report is returned even if links are
+    /// broken, see [`LinkReport::into_result`].
+    pub fn check(
+        &self,
+        output_dir: impl AsRef<Path>,
+        domain: Option<&str>,
+    ) -> Result<LinkReport, SsgError> {
+        let output_dir = output_dir.as_ref();
+        let mut pages = Vec::new();
+        collect_pages(output_dir, Path::new(""), &mut pages)?;
+        pages.sort();
+
+        let mut scanned = BTreeMap::new();
+        for page in &pages {
+            let file = output_dir.join(page);
+            let html = fs::read_to_string(&file).with_path(&file)?;
+            scanned.insert(page.clone(), scan_page(&html)?);
+        }
+
+        let domain = domain.map(|domain| domain.trim_end_matches('/'));
+        let mut report = LinkReport {
+            pages: pages.len(),
+            ..Default::default()
+        };
+        let mut linked = BTreeSet::new();
+
+        for (page, scan) in &scanned {
+            for (href, line) in &scan.links {
+                let Some((target, fragment)) = self.target(page, href, domain) else {
+                    continue;
+                };
+                report.links += 1;
+
+                let problem = match normalize(&target).map(|target| resolve(output_dir, &target)) {
+                    None => Some(LinkProblem::OutsideSite),
+                    Some(None) => Some(LinkProblem::MissingFile),
+                    Some(Some(file)) => {
+                        if file != *page {
+                            linked.insert(file.clone());
+                        }
+                        let anchors = scanned.get(&file).map(|scan| &scan.anchors);
+                        match (anchors, fragment) {
+                            (Some(anchors), Some(fragment))
+                                if fragment != "top" && !anchors.contains(&fragment) =>
+                            {
+                                Some(LinkProblem::MissingAnchor)
+                            }
+                            _ => None,
+                        }
+                    }
+                };
+                if let Some(problem) = problem {
+                    report.broken.push(BrokenLink {
+                        page: page.clone(),
+                        line: *line,
+                        href: href.clone(),
+                        problem,
+                    });
+                }
+            }
+        }
+
+        if self.orphans {
+            report.orphans = scanned
+                .iter()
+                .filter(|(page, scan)| {
+                    !scan.redirect
+                        && !linked.contains(*page)
+                        && page.as_path() != Path::new("index.html")
+                        && page.file_name().is_some_and(|name| name != "404.html")
+                })
+                .map(|(page, _)| page.clone())
+                .collect();
+        }
+
+        Ok(report)
+    }
+
+    /// Site path (relative to the output directory, with `.` and `..` segments still in place)
+    /// and fragment of an internal link; `None` for external and ignored links
+    fn target(
+        &self,
+        page: &Path,
+        href: &str,
+        domain: Option<&str>,
+    ) -> Option<(String, Option<String>)> {
+        let href = href.trim();
+        if href.is_empty() || self.ignore.iter().any(|prefix| href.starts_with(prefix)) {
+            return None;
+        }
+
+        let href = match domain.and_then(|domain| href.strip_prefix(domain)) {
+            Some(rest) if rest.is_empty() || rest.starts_with(['/', '?', '#']) => {
+                if rest.starts_with('/') {
+                    rest.to_string()
+                } else {
+                    format!("/{}", rest)
+                }
+            }
+            _ if is_external(href) => return None,
+            _ => href.to_string(),
+        };
+        if self.ignore.iter().any(|prefix| href.starts_with(prefix)) {
+            return None;
+        }
+
+        let (path, fragment) = match href.split_once('#') {
+            Some((path, fragment)) => (path, Some(percent_decode(fragment))),
+            None => (href.as_str(), None),
+        };
+        let path = path.split('?').next().unwrap_or_default();
+        let fragment = fragment.filter(|fragment| !fragment.is_empty());
+
+        let target = if path.is_empty() {
+            page.to_string_lossy().replace('\\', "/")
+        } else if let Some(absolute) = path.strip_prefix('/') {
+            absolute.to_string()
+        } else {
+            let dir = page
+                .parent()
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            format!("{}/{}", dir, path)
+        };
+        Some((percent_decode(&target), fragment))
+    }
+}
+
+/// Collect the HTML files below `dir`, relative to the output directory
+fn collect_pages(root: &Path, dir: &Path, pages: &mut Vec<PathBuf>) -> Result<(), SsgError> {
+    let path = root.join(dir);
+    for entry in fs::read_dir(&path).with_path(&path)? {
+        let entry = entry.with_path(&path)?;
+        let relative = dir.join(entry.file_name());
+        if entry.path().is_dir() {
+            collect_pages(root, &relative, pages)?;
+        } else if relative.extension().is_some_and(|ext| ext == "html") {
+            pages.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Collect the links and anchors of a page
+fn scan_page(html: &str) -> Result<ScannedPage, SsgError> {
+    let page = RefCell::new(ScannedPage::default());
+    // Links are searched in the source in document order to find their lines
+    let cursor = RefCell::new(0);
+
+    let mut handlers = vec![
+        element!("[id]", |el| {
+            if let Some(id) = el.get_attribute("id") {
+                page.borrow_mut().anchors.insert(id);
+            }
+            Ok(())
+        }),
+        element!("a[name]", |el| {
+            if let Some(name) = el.get_attribute("name") {
+                page.borrow_mut().anchors.insert(name);
+            }
+            Ok(())
+        }),
+        element!("meta[http-equiv]", |el| {
+            if el
+                .get_attribute("http-equiv")
+                .is_some_and(|value| value.eq_ignore_ascii_case("refresh"))
+            {
+                page.borrow_mut().redirect = true;
+            }
+            Ok(())
+        }),
+    ];
+    for (selector, attribute) in REFERENCES {
+        handlers.push(element!(selector, |el| {
+            if let Some(href) = el.get_attribute(attribute) {
+                let line = find_line(html, &href, &mut cursor.borrow_mut());
+                page.borrow_mut().links.push((href, line));
+            }
+            Ok(())
+        }));
+    }
+
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: handlers,
+            ..Settings::default()
+        },
+        |_: &[u8]| {},
+    );
+    rewriter
+        .write(html.as_bytes())
+        .and_then(|_| rewriter.end())
+        .map_err(SsgError::custom)?;
+
+    Ok(page.into_inner())
+}
+
+/// Line of the next occurrence of an attribute value, starting the search at `cursor`
+fn find_line(html: &str, value: &str, cursor: &mut usize) -> Option<usize> {
+    let escaped = value.replace('&', "&amp;");
+    let rest = html.get(*cursor..)?;
+    let offset = *cursor + rest.find(value).or_else(|| rest.find(&escaped))?;
+    *cursor = offset + 1;
+    Some(html[..offset].matches('\n').count() + 1)
+}
+
+/// File of a site path: the file itself, its `index.html` or its `.html` sibling
+fn resolve(output_dir: &Path, target: &str) -> Option<PathBuf> {
+    let trimmed = target.trim_end_matches('/');
+    let candidates = [
+        (!target.ends_with('/') && !target.is_empty()).then(|| PathBuf::from(target)),
+        Some(Path::new(trimmed).join("index.html")),
+        (!trimmed.is_empty()).then(|| PathBuf::from(format!("{}.html", trimmed))),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|candidate| output_dir.join(candidate).is_file())
+}
+
+/// Resolve `.` and `..` segments; `None` for paths leaving the output directory
+fn normalize(path: &str) -> Option<String> {
+    let mut segments: Vec<&str> = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(segment) => segments.push(segment.to_str()?),
+            Component::ParentDir => {
+                segments.pop()?;
+            }
+            _ => {}
+        }
+    }
+    let mut normalized = segments.join("/");
+    if path.ends_with('/') && !normalized.is_empty() {
+        normalized.push('/');
+    }
+    Some(normalized)
+}
+
+fn is_external(href: &str) -> bool {
+    if href.starts_with("//") {
+        return true;
+    }
+    // A scheme (`https:`, `mailto:`, `data:`, ...) before the first path separator
+    href.split(['/', '?', '#'])
+        .next()
+        .is_some_and(|first| first.contains(':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, file: &str, html: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, html).unwrap();
+    }
+
+    #[test]
+    fn test_link_check() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "index.html",
+            r##"<html><head>
+<link rel="canonical" href="https://example.com/">
+<link rel="stylesheet" href="style.css">
+</head><body>
+<a href="/about">About</a>
+<a href="/about#team">Team</a>
+<a href="/about/#jobs">Jobs</a>
+<a href="https://example.com/blog/">Blog</a>
+<a href="/missing">Missing</a>
+<img src="/logo.png">
+<a href="#top">Top</a>
+<a href="https://other.com/x">Other</a>
+<a href="mailto:team@example.com">Mail</a>
+<a href="/api/v1">API</a>
+</body></html>"##,
+        );
+        write(dir.path(), "style.css", "");
+        write(
+            dir.path(),
+            "about/index.html",
+            r#"<h2 id="team">Team</h2><a href="../">Home</a>"#,
+        );
+        write(
+            dir.path(),
+            "blog/index.html",
+            r#"<a href="../about">About</a><a href="../../etc/passwd">Up</a>"#,
+        );
+        write(dir.path(), "orphan/index.html", "<p>Nobody links here</p>");
+        write(dir.path(), "404.html", "<p>Not found</p>");
+        write(
+            dir.path(),
+            "old/index.html",
+            r#"<meta http-equiv="refresh" content="0; url=/about">"#,
+        );
+
+        let report = LinkCheck::default()
+            .ignore("/api/")
+            .check(dir.path(), Some("https://example.com/"))
+            .unwrap();
+
+        assert_eq!(report.pages, 6);
+        let broken: Vec<_> = report.broken.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            broken,
+            vec![
+                "blog/index.html:1: ../../etc/passwd (outside the site)",
+                "index.html:7: /about/#jobs (missing anchor)",
+                "index.html:9: /missing (missing file)",
+                "index.html:10: /logo.png (missing file)",
+            ]
+        );
+        assert_eq!(report.orphans, vec![PathBuf::from("orphan/index.html")]);
+
+        let error = LinkCheck::default()
+            .check(dir.path(), None)
+            .unwrap()
+            .into_result()
+            .unwrap_err();
+        assert!(matches!(error, SsgError::BrokenLinks { .. }));
+    }
+}
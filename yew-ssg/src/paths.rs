@@ -0,0 +1,100 @@
+//! Helpers for URL paths and route patterns, shared by the generator, feeds, the link check
+//! and the dev server.
+
+use std::collections::HashMap;
+
+/// Parameters of `path` for a route pattern such as `/crate/:id`, if the pattern matches
+pub(crate) fn match_pattern(pattern: &str, path: &str) -> Option<HashMap<String, String>> {
+    let mut params = HashMap::new();
+    let mut segments = path.trim_matches('/').split('/');
+    for part in pattern.trim_matches('/').split('/') {
+        if let Some(name) = part.strip_prefix('*') {
+            let rest: Vec<_> = segments.by_ref().collect();
+            if !name.is_empty() {
+                params.insert(name.to_string(), rest.join("/"));
+            }
+            return Some(params);
+        }
+        let segment = segments.next()?;
+        match part.strip_prefix(':') {
+            Some(name) => {
+                params.insert(name.to_string(), segment.to_string());
+            }
+            None if part == segment => {}
+            None => return None,
+        }
+    }
+    segments.next().is_none().then_some(params)
+}
+
+/// Path of a route below its language prefix (`/de/crate/x` → `/crate/x` for `de`), if the path
+/// starts with that prefix
+pub(crate) fn strip_lang_prefix<'a>(path: &'a str, lang: Option<&str>) -> Option<&'a str> {
+    let rest = path.strip_prefix('/')?.strip_prefix(lang?)?;
+    match rest {
+        "" => Some("/"),
+        rest if rest.starts_with('/') => Some(rest),
+        _ => None,
+    }
+}
+
+/// Decode the `%XX` escapes of a path or fragment
+pub(crate) fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_pattern() {
+        assert_eq!(
+            match_pattern("/crate/:id", "/crate/yew-ssg"),
+            Some(HashMap::from([("id".to_string(), "yew-ssg".to_string())]))
+        );
+        assert_eq!(match_pattern("/crate/:id", "/crate"), None);
+        assert_eq!(match_pattern("/about", "/about/team"), None);
+        assert_eq!(
+            match_pattern("/docs/*rest", "/docs/a/b"),
+            Some(HashMap::from([("rest".to_string(), "a/b".to_string())]))
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("/yew%2Dssg/caf%C3%A9"), "/yew-ssg/café");
+        assert_eq!(percent_decode("/100%"), "/100%");
+        assert_eq!(percent_decode("/%zz"), "/%zz");
+    }
+
+    #[test]
+    fn test_strip_lang_prefix() {
+        assert_eq!(
+            strip_lang_prefix("/de/crate/x", Some("de")),
+            Some("/crate/x")
+        );
+        assert_eq!(strip_lang_prefix("/de", Some("de")), Some("/"));
+        assert_eq!(strip_lang_prefix("/design", Some("de")), None);
+        assert_eq!(strip_lang_prefix("/crate/x", None), None);
+    }
+}
@@ -0,0 +1,188 @@
+//! Build lifecycle hooks.
+//!
+//! Generators and processors work on a single page. A [`BuildPlugin`] follows the whole
+//! build instead: it is told when the build starts, before and after every route, and gets
+//! all generated pages at the end, so it can write site-wide outputs such as search indexes
+//! or reports.
+//!
+//! The route hooks run for every `generate*` call; `before_build` and `after_build` run in
+//! [`StaticSiteGenerator::build`](crate::StaticSiteGenerator::build).
+
+use crate::config::SsgConfig;
+use crate::error::SsgError;
+use crate::report::PageStatus;
+use crate::template_context::PageInfo;
+use std::any::Any;
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+/// A page produced by the build
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedPage {
+    /// Route, parameters, language and metadata of the page
+    pub page: PageInfo,
+    /// Output file, relative to the output directory
+    pub output_file: PathBuf,
+    /// Final HTML of the page
+    pub html: String,
+    /// Whether the file was written or left unchanged
+    pub status: PageStatus,
+}
+
+/// Hooks into the lifecycle of a build
+pub trait BuildPlugin: Debug + Send + Sync {
+    /// Returns the unique name of this plugin
+    fn name(&self) -> &'static str;
+
+    /// Called once before any route is rendered
+    fn before_build(&self, _config: &SsgConfig) -> Result<(), SsgError> {
+        Ok(())
+    }
+
+    /// Called before a route is rendered; an error fails the route
+    fn before_route(&self, _config: &SsgConfig, _page: &PageInfo) -> Result<(), SsgError> {
+        Ok(())
+    }
+
+    /// Called after a page has been generated; an error fails the route
+    fn after_route(&self, _config: &SsgConfig, _page: &GeneratedPage) -> Result<(), SsgError> {
+        Ok(())
+    }
+
+    /// Called once with every page generated by the build
+    fn after_build(&self, _config: &SsgConfig, _pages: &[GeneratedPage]) -> Result<(), SsgError> {
+        Ok(())
+    }
+
+    /// Creates a boxed clone of this plugin
+    fn clone_box(&self) -> Box<dyn BuildPlugin>;
+
+    /// Returns a reference to self as Any for downcasting
+    fn as_any(&self) -> &dyn Any;
+}
+
+// Enable cloning for trait objects
+impl Clone for Box<dyn BuildPlugin> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::failure::{BuildStage, FailurePolicy};
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+    use std::fs;
+    use std::sync::{Arc, Mutex};
+
+    /// Records its hooks and writes a search index of all pages
+    #[derive(Debug, Clone, Default)]
+    struct SearchIndexPlugin {
+        events: Arc<Mutex<Vec<String>>>,
+        reject: Option<String>,
+    }
+
+    impl BuildPlugin for SearchIndexPlugin {
+        fn name(&self) -> &'static str {
+            "search_index"
+        }
+
+        fn before_build(&self, _config: &SsgConfig) -> Result<(), SsgError> {
+            self.events.lock().unwrap().push("before_build".to_string());
+            Ok(())
+        }
+
+        fn before_route(&self, _config: &SsgConfig, page: &PageInfo) -> Result<(), SsgError> {
+            if self.reject.as_deref() == Some(page.path.as_str()) {
+                return Err("rejected".into());
+            }
+            Ok(())
+        }
+
+        fn after_route(&self, _config: &SsgConfig, page: &GeneratedPage) -> Result<(), SsgError> {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("after_route {}", page.page.path));
+            Ok(())
+        }
+
+        fn after_build(&self, config: &SsgConfig, pages: &[GeneratedPage]) -> Result<(), SsgError> {
+            let index: Vec<_> = pages
+                .iter()
+                .map(|page| (page.page.url.clone(), page.output_file.clone()))
+                .collect();
+            let file = config.output_dir.join("search-index.json");
+            fs::write(&file, serde_json::to_string(&index)?).map_err(|e| SsgError::io(file, e))?;
+            self.events.lock().unwrap().push("after_build".to_string());
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn crate::plugin::BuildPlugin> {
+            Box::new(self.clone())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_plugin_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugin = SearchIndexPlugin::default();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .add_route_param("/crate/:id", "id", ["a"])
+            .add_plugin(plugin.clone())
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        // Pages of direct `generate` calls are not kept for a later build
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let report = generator
+            .build::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        assert_eq!(report.pages.len(), 3);
+
+        let events = plugin.events.lock().unwrap().clone();
+        let events = &events[events.iter().position(|e| e == "before_build").unwrap()..];
+        assert_eq!(events.last().map(String::as_str), Some("after_build"));
+        assert!(events.contains(&"after_route /crate/a".to_string()));
+
+        let index = fs::read_to_string(dir.path().join("search-index.json")).unwrap();
+        assert_eq!(
+            index,
+            r#"[["/","index.html"],["/crate/","crate/index.html"],["/crate/a","crate/a/index.html"]]"#
+        );
+
+        // A failing route hook fails the route like any other stage
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .add_plugin(SearchIndexPlugin {
+                reject: Some("/".to_string()),
+                ..Default::default()
+            })
+            .failure_policy(FailurePolicy::SkipRoute)
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        let report = generator
+            .build::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        assert_eq!(report.failures[0].stage, BuildStage::Plugin);
+        assert_eq!(
+            report.failures[0].component.as_deref(),
+            Some("search_index")
+        );
+        assert!(!dir.path().join("index.html").exists());
+    }
+}
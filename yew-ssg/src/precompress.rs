@@ -0,0 +1,295 @@
+//! Pre-compressed siblings of the output files.
+//!
+//! Static hosts such as nginx (`gzip_static`, `brotli_static`), Caddy or Netlify serve
+//! `about/index.html.gz` or `about/index.html.br` instead of compressing `about/index.html`
+//! on every request. [`Precompress`] writes these siblings for the generated HTML, XML and
+//! JSON files and for copied assets above a size threshold. Files that do not get smaller
+//! are skipped, and siblings newer than their file are kept.
+
+use crate::build_manifest::MANIFEST_FILE_NAME;
+use crate::error::{IoContext, SsgError};
+use crate::report::REPORT_FILE_NAME;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Extensions of generated files, compressed regardless of their size
+const GENERATED_EXTENSIONS: [&str; 3] = ["html", "xml", "json"];
+
+/// Brotli quality (0-11) and window size used for the siblings
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Which compressed siblings to write
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Precompress {
+    /// Write `.gz` siblings
+    #[serde(default = "default_true")]
+    pub gzip: bool,
+    /// Write `.br` siblings
+    #[serde(default = "default_true")]
+    pub brotli: bool,
+    /// Minimum size in bytes of other files (copied assets) to compress
+    #[serde(default = "default_min_size")]
+    pub min_size: u64,
+    /// Extensions of other files to compress when they reach `min_size`
+    #[serde(default = "default_asset_extensions")]
+    pub asset_extensions: Vec<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_size() -> u64 {
+    1024
+}
+
+fn default_asset_extensions() -> Vec<String> {
+    [
+        "css",
+        "js",
+        "mjs",
+        "map",
+        "svg",
+        "wasm",
+        "txt",
+        "ico",
+        "webmanifest",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+impl Default for Precompress {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            brotli: true,
+            min_size: default_min_size(),
+            asset_extensions: default_asset_extensions(),
+        }
+    }
+}
+
+/// Compressed siblings of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressedFile {
+    /// File, relative to the output directory
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub bytes: u64,
+    /// Size of the `.gz` sibling; `None` if not written because it would not be smaller
+    pub gzip: Option<u64>,
+    /// Size of the `.br` sibling; `None` if not written because it would not be smaller
+    pub brotli: Option<u64>,
+}
+
+impl CompressedFile {
+    /// Bytes saved by the smallest compressed sibling
+    pub fn savings(&self) -> u64 {
+        self.gzip
+            .into_iter()
+            .chain(self.brotli)
+            .min()
+            .map_or(0, |smallest| self.bytes - smallest)
+    }
+}
+
+impl Precompress {
+    /// Write gzip siblings only
+    pub fn gzip_only() -> Self {
+        Self {
+            brotli: false,
+            ..Self::default()
+        }
+    }
+
+    /// Set the minimum size of copied assets to compress
+    pub fn min_size(mut self, bytes: u64) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    /// Write the siblings of every eligible file below `output_dir`
+    pub fn compress_dir(&self, output_dir: &Path) -> Result<Vec<CompressedFile>, SsgError> {
+        let mut files = Vec::new();
+        collect_files(output_dir, &mut files)?;
+        files.sort();
+
+        let mut compressed = Vec::new();
+        for file in files {
+            let bytes = fs::metadata(&file).with_path(&file)?.len();
+            if !self.is_eligible(&file, bytes) {
+                continue;
+            }
+
+            let mut content = None;
+            let mut sibling = |enabled: bool, extension: &str, compress: fn(&[u8]) -> Vec<u8>| {
+                if !enabled {
+                    return Ok(None);
+                }
+                let sibling = sibling_path(&file, extension);
+                if is_up_to_date(&file, &sibling) {
+                    return Ok(Some(fs::metadata(&sibling).with_path(&sibling)?.len()));
+                }
+                if content.is_none() {
+                    content = Some(fs::read(&file).with_path(&file)?);
+                }
+                let data = compress(content.as_deref().unwrap_or_default());
+                if data.len() as u64 >= bytes {
+                    // Remove siblings of earlier builds, which no longer match the file
+                    if sibling.exists() {
+                        fs::remove_file(&sibling).with_path(&sibling)?;
+                    }
+                    return Ok(None);
+                }
+                fs::write(&sibling, &data).with_path(&sibling)?;
+                Ok::<_, SsgError>(Some(data.len() as u64))
+            };
+            let gzip = sibling(self.gzip, "gz", gzip)?;
+            let brotli = sibling(self.brotli, "br", brotli)?;
+
+            compressed.push(CompressedFile {
+                path: file.strip_prefix(output_dir).unwrap_or(&file).to_path_buf(),
+                bytes,
+                gzip,
+                brotli,
+            });
+        }
+        Ok(compressed)
+    }
+
+    /// Whether a file gets compressed siblings
+    fn is_eligible(&self, file: &Path, bytes: u64) -> bool {
+        let name = file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        if name == REPORT_FILE_NAME || name == MANIFEST_FILE_NAME {
+            return false;
+        }
+        let Some(extension) = file.extension().and_then(|ext| ext.to_str()) else {
+            return false;
+        };
+        GENERATED_EXTENSIONS.contains(&extension)
+            || (bytes >= self.min_size && self.asset_extensions.iter().any(|e| e == extension))
+    }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+    // Writing to a Vec does not fail
+    let _ = encoder.write_all(data);
+    encoder.finish().unwrap_or_default()
+}
+
+fn brotli(data: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    {
+        let mut writer =
+            brotli::CompressorWriter::new(&mut compressed, 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+        let _ = writer.write_all(data);
+    }
+    compressed
+}
+
+/// `index.html` → `index.html.gz`
+fn sibling_path(file: &Path, extension: &str) -> PathBuf {
+    let mut name = file.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// Whether `sibling` was written after the last change of `file`
+fn is_up_to_date(file: &Path, sibling: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    match (modified(file), modified(sibling)) {
+        (Some(file), Some(sibling)) => sibling >= file,
+        _ => false,
+    }
+}
+
+/// Collect the files below `dir`, leaving out compressed siblings
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), SsgError> {
+    for entry in fs::read_dir(dir).with_path(dir)? {
+        let path = entry.with_path(dir)?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if !path
+            .extension()
+            .is_some_and(|ext| ext == "gz" || ext == "br")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    #[test]
+    fn test_compressed_siblings() {
+        let dir = tempfile::tempdir().unwrap();
+        let page =
+            "<html><body>".to_string() + &"<p>Hello, world!</p>".repeat(100) + "</body></html>";
+        fs::create_dir_all(dir.path().join("about")).unwrap();
+        fs::write(dir.path().join("about/index.html"), &page).unwrap();
+        fs::write(dir.path().join("tiny.json"), "{}").unwrap();
+        fs::write(dir.path().join("small.css"), "body{margin:0}").unwrap();
+        fs::write(dir.path().join("logo.png"), vec![0u8; 4096]).unwrap();
+        fs::write(dir.path().join(REPORT_FILE_NAME), &page).unwrap();
+
+        let compressed = Precompress::default().compress_dir(dir.path()).unwrap();
+
+        // Assets below the size threshold, other file types and the report are left out
+        let paths: Vec<_> = compressed.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("about/index.html"),
+                PathBuf::from("tiny.json")
+            ]
+        );
+
+        let about = &compressed[0];
+        assert_eq!(about.bytes, page.len() as u64);
+        assert!(about.gzip.unwrap() < about.bytes);
+        assert!(about.brotli.unwrap() < about.bytes);
+        assert_eq!(
+            about.savings(),
+            about.bytes - about.gzip.unwrap().min(about.brotli.unwrap())
+        );
+
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(dir.path().join("about/index.html.gz")).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, page);
+        let mut decoded = Vec::new();
+        brotli::Decompressor::new(
+            fs::File::open(dir.path().join("about/index.html.br")).unwrap(),
+            4096,
+        )
+        .read_to_end(&mut decoded)
+        .unwrap();
+        assert_eq!(decoded, page.as_bytes());
+
+        // Compression does not help for tiny files
+        assert_eq!((compressed[1].gzip, compressed[1].brotli), (None, None));
+        assert_eq!(compressed[1].savings(), 0);
+        assert!(!dir.path().join("tiny.json.gz").exists());
+
+        // A second run keeps the up-to-date siblings and does not compress them again
+        let again = Precompress::gzip_only().compress_dir(dir.path()).unwrap();
+        assert_eq!(again[0].gzip, about.gzip);
+        assert_eq!(again[0].brotli, None);
+    }
+}
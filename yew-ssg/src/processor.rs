@@ -7,8 +7,8 @@
 //! - Modifying HTML attributes based on metadata
 //! - Transforming content structure as needed
 
+use crate::error::SsgError;
 use std::collections::HashMap;
-use std::error::Error;
 use std::fmt::Debug;
 
 /// The `Processor` trait defines how HTML content is transformed during the
@@ -25,7 +25,7 @@ use std::fmt::Debug;
 /// ```
 /// use yew_ssg::processor::Processor;
 /// use std::collections::HashMap;
-/// use std::error::Error;
+/// use yew_ssg::SsgError;
 ///
 /// #[derive(Debug, Clone)]
 /// struct PlaceholderProcessor;
@@ -41,7 +41,7 @@ use std::fmt::Debug;
 ///         _metadata: &HashMap<String, String>,
 ///         _generator_outputs: &HashMap<String, String>,
 ///         _content: &str,
-///     ) -> Result<String, Box<dyn Error>> {
+///     ) -> Result<String, SsgError> {
 ///         Ok(html.replace("__PLACEHOLDER__", "Replaced Content"))
 ///     }
 ///
@@ -70,14 +70,14 @@ pub trait Processor: Debug + Send + Sync {
     ///
     /// # Returns
     ///
-    /// * `Result<String, Box<dyn Error>>` - The processed HTML or an error
+    /// * `Result<String, SsgError>` - The processed HTML or an error
     fn process(
         &self,
         html: &str,
         metadata: &HashMap<String, String>,
         generator_outputs: &HashMap<String, String>,
         content: &str,
-    ) -> Result<String, Box<dyn Error>>;
+    ) -> Result<String, SsgError>;
 
     /// Creates a boxed clone of this processor.
     ///
@@ -130,7 +130,7 @@ pub mod tests {
             _metadata: &HashMap<String, String>,
             _generator_outputs: &HashMap<String, String>,
             _content: &str,
-        ) -> Result<String, Box<dyn Error>> {
+        ) -> Result<String, SsgError> {
             Ok((self.transform_fn)(html))
         }
 
@@ -1,12 +1,18 @@
+use crate::error::SsgError;
 use crate::processor::Processor;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct ProcessorCollection {
     processors: Vec<Box<dyn Processor>>,
 }
 
+impl Default for ProcessorCollection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProcessorCollection {
     pub fn new() -> Self {
         Self {
@@ -18,17 +24,23 @@ impl ProcessorCollection {
         self.processors.push(Box::new(processor));
     }
 
+    /// Run all processors in order.
+    ///
+    /// Errors are attributed to the failing processor and the route in the `path` metadata.
     pub fn process_all(
         &self,
         html: &str,
         metadata: &HashMap<String, String>,
         generator_outputs: &HashMap<String, String>,
         content: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         let mut result = html.to_string();
+        let route = metadata.get("path").map(String::as_str).unwrap_or_default();
 
         for processor in &self.processors {
-            result = processor.process(&result, metadata, generator_outputs, content)?;
+            result = processor
+                .process(&result, metadata, generator_outputs, content)
+                .map_err(|error| error.in_processor(route, processor.name()))?;
         }
 
         Ok(result)
@@ -0,0 +1,252 @@
+use crate::assets::AssetManifest;
+use crate::error::SsgError;
+use crate::processor::Processor;
+use lol_html::{RewriteStrSettings, element, rewrite_str};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Attributes holding a single asset reference
+const URL_ATTRIBUTES: [&str; 3] = ["href", "src", "poster"];
+
+/// Processor that points asset references at their output paths.
+///
+/// Root-relative `href`, `src`, `srcset` and `poster` attributes naming an asset of
+/// the [`AssetManifest`] are rewritten, as are `og:image` and `twitter:image` meta tags,
+/// which may also use an absolute URL on the site domain. Query strings and fragments are
+/// kept.
+#[derive(Debug, Clone)]
+pub struct AssetProcessor {
+    manifest: Arc<AssetManifest>,
+    base_path: String,
+    domain: Option<String>,
+}
+
+impl AssetProcessor {
+    /// Creates an AssetProcessor for the given manifest
+    pub fn new(manifest: AssetManifest) -> Self {
+        Self {
+            manifest: Arc::new(manifest),
+            base_path: String::new(),
+            domain: None,
+        }
+    }
+
+    /// Sets the path the site is served below (e.g. `/my-repo` on GitHub Pages)
+    pub fn with_base_path(mut self, base_path: &str) -> Self {
+        self.base_path = match base_path.trim_matches('/') {
+            "" => String::new(),
+            prefix => format!("/{}", prefix),
+        };
+        self
+    }
+
+    /// Sets the site domain, so absolute image URLs on it are rewritten too
+    pub fn with_domain(mut self, domain: Option<&str>) -> Self {
+        self.domain = domain.map(|domain| domain.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// The manifest used for rewriting
+    pub fn manifest(&self) -> &AssetManifest {
+        &self.manifest
+    }
+
+    /// Output URL of a reference, `None` if it does not name an asset
+    fn rewrite(&self, url: &str) -> Option<String> {
+        if let Some(domain) = &self.domain
+            && let Some(path) = url.strip_prefix(domain.as_str())
+        {
+            return self
+                .manifest
+                .rewrite(path, &self.base_path)
+                .map(|path| format!("{}{}", domain, path));
+        }
+        self.manifest.rewrite(url, &self.base_path)
+    }
+
+    /// Rewrite the candidates of a `srcset` attribute (`/a.png 1x, /b.png 2x`), `None` if no
+    /// candidate names an asset. `data:` candidates are kept as they are.
+    fn rewrite_srcset(&self, srcset: &str) -> Option<String> {
+        let mut candidates = Vec::new();
+        let mut rewritten = false;
+        let mut rest = srcset;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+            if rest.is_empty() {
+                break;
+            }
+            // The URL runs up to the next whitespace, so commas inside `data:` URLs stay in it
+            let (url, after) = rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+            let (url, descriptor) = match url.strip_suffix(',') {
+                Some(_) => {
+                    rest = after;
+                    (url.trim_end_matches(','), "")
+                }
+                None => {
+                    let (descriptor, next) = after.split_once(',').unwrap_or((after, ""));
+                    rest = next;
+                    (url, descriptor.trim())
+                }
+            };
+
+            let url = match self.rewrite(url).filter(|_| !url.starts_with("data:")) {
+                Some(output) => {
+                    rewritten = true;
+                    output
+                }
+                None => url.to_string(),
+            };
+            candidates.push(if descriptor.is_empty() {
+                url
+            } else {
+                format!("{} {}", url, descriptor)
+            });
+        }
+        rewritten.then(|| candidates.join(", "))
+    }
+}
+
+impl Processor for AssetProcessor {
+    fn name(&self) -> &'static str {
+        "asset_processor"
+    }
+
+    fn process(
+        &self,
+        html: &str,
+        _metadata: &HashMap<String, String>,
+        _generator_outputs: &HashMap<String, String>,
+        _content: &str,
+    ) -> Result<String, SsgError> {
+        if self.manifest.files.is_empty() {
+            return Ok(html.to_string());
+        }
+
+        rewrite_str(
+            html,
+            RewriteStrSettings {
+                element_content_handlers: vec![
+                    element!("[href], [src], [poster]", |el| {
+                        for attribute in URL_ATTRIBUTES {
+                            if let Some(url) = el.get_attribute(attribute)
+                                && let Some(rewritten) = self.rewrite(&url)
+                            {
+                                el.set_attribute(attribute, &rewritten)?;
+                            }
+                        }
+                        Ok(())
+                    }),
+                    element!("[srcset]", |el| {
+                        if let Some(srcset) = el.get_attribute("srcset")
+                            && let Some(rewritten) = self.rewrite_srcset(&srcset)
+                        {
+                            el.set_attribute("srcset", &rewritten)?;
+                        }
+                        Ok(())
+                    }),
+                    element!(
+                        r#"meta[property="og:image"], meta[name="twitter:image"]"#,
+                        |el| {
+                            if let Some(url) = el.get_attribute("content")
+                                && let Some(rewritten) = self.rewrite(&url)
+                            {
+                                el.set_attribute("content", &rewritten)?;
+                            }
+                            Ok(())
+                        }
+                    ),
+                ],
+                ..RewriteStrSettings::new()
+            },
+        )
+        .map_err(SsgError::custom)
+    }
+
+    fn clone_box(&self) -> Box<dyn Processor> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_rewrite_asset_references() {
+        let manifest = AssetManifest {
+            files: BTreeMap::from([
+                (
+                    "css/style.css".to_string(),
+                    "css/style.3f9a1c.css".to_string(),
+                ),
+                (
+                    "img/logo.png".to_string(),
+                    "img/logo.8b2e4d.png".to_string(),
+                ),
+                (
+                    "img/logo@2x.png".to_string(),
+                    "img/logo@2x.1c7f00.png".to_string(),
+                ),
+            ]),
+        };
+        let processor = AssetProcessor::new(manifest)
+            .with_base_path("blog/")
+            .with_domain(Some("https://example.com/"));
+
+        let html = r#"<head>
+<link rel="stylesheet" href="/blog/css/style.css?v=2">
+<link rel="canonical" href="https://example.com/blog/about">
+<meta property="og:image" content="https://example.com/blog/img/logo.png">
+<meta name="twitter:image" content="/blog/img/logo.png">
+</head><body>
+<img src="/blog/img/logo.png" srcset="/blog/img/logo.png 1x, /blog/img/logo@2x.png 2x">
+<a href="/blog/about">About</a><img src="img/logo.png"><img src="/img/logo.png">
+</body>"#;
+
+        let result = processor
+            .process(html, &HashMap::new(), &HashMap::new(), "")
+            .unwrap();
+
+        assert!(result.contains(r#"href="/blog/css/style.3f9a1c.css?v=2""#));
+        assert!(result.contains(r#"href="https://example.com/blog/about""#));
+        assert!(result.contains(
+            r#"<meta property="og:image" content="https://example.com/blog/img/logo.8b2e4d.png">"#
+        ));
+        assert!(
+            result.contains(r#"<meta name="twitter:image" content="/blog/img/logo.8b2e4d.png">"#)
+        );
+        assert!(result.contains(
+            r#"<img src="/blog/img/logo.8b2e4d.png" srcset="/blog/img/logo.8b2e4d.png 1x, /blog/img/logo@2x.1c7f00.png 2x">"#
+        ));
+        // Relative references and references outside the base path are left alone
+        assert!(result.contains(r#"<img src="img/logo.png"><img src="/img/logo.png">"#));
+    }
+
+    #[test]
+    fn test_srcset_with_data_urls() {
+        let manifest = AssetManifest {
+            files: BTreeMap::from([(
+                "img/logo@2x.png".to_string(),
+                "img/logo@2x.1c7f00.png".to_string(),
+            )]),
+        };
+        let processor = AssetProcessor::new(manifest);
+
+        let html = r#"<img srcset="data:image/png;base64,iVBOR,w0K 1x, /img/logo@2x.png 2x">
+<img srcset="data:image/gif;base64,R0lG,ODlh 1x,/img/other.png  2x">"#;
+        let result = processor
+            .process(html, &HashMap::new(), &HashMap::new(), "")
+            .unwrap();
+
+        assert!(result.contains(
+            r#"<img srcset="data:image/png;base64,iVBOR,w0K 1x, /img/logo@2x.1c7f00.png 2x">"#
+        ));
+        // Attributes without assets are left untouched
+        assert!(
+            result.contains(
+                r#"<img srcset="data:image/gif;base64,R0lG,ODlh 1x,/img/other.png  2x">"#
+            )
+        );
+    }
+}
@@ -16,7 +16,7 @@ pub fn process_element(
     // Collect data-ssg-* attributes first
     let attrs_to_remove: Vec<String> = element
         .attributes()
-        .into_iter()
+        .iter()
         .filter(|attr| attr.name().starts_with("data-ssg-"))
         .map(|attr| attr.name().to_string())
         .collect();
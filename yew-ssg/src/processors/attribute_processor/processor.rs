@@ -1,10 +1,10 @@
+use crate::error::SsgError;
 use crate::generator_collection::GeneratorCollection;
 use crate::processor::Processor;
 use crate::processors::attribute_processor::{SsgAttribute, process_element};
 use log::{debug, warn};
 use lol_html::{HtmlRewriter, Settings, element};
 use std::collections::HashMap;
-use std::error::Error;
 use std::fmt;
 use std::sync::Arc;
 
@@ -62,7 +62,7 @@ impl AttributeProcessor {
         route: &str,
         content: &str,
         metadata: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         for generator in generators.iter() {
             match generator.generate(key, route, content, metadata) {
                 Ok(output) => return Ok(output),
@@ -84,7 +84,7 @@ impl Processor for AttributeProcessor {
         metadata: &HashMap<String, String>,
         generator_outputs: &HashMap<String, String>,
         content: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         debug!("Processing HTML with AttributeProcessor");
         let route = metadata.get("route").map_or("/", |r| r.as_str());
         let prefix = self.prefix.clone();
@@ -169,7 +169,7 @@ impl Processor for AttributeProcessor {
             // Collect attribute names and values for data-ssg-*
             let data_attrs: Vec<(String, String)> = el
                 .attributes()
-                .into_iter()
+                .iter()
                 .filter(|attr| {
                     attr.name().starts_with(&prefix_dash) && attr.name() != placeholder_attr
                     // Skip placeholder attribute as we already handled it
@@ -221,11 +221,11 @@ impl Processor for AttributeProcessor {
         );
 
         // Process the HTML
-        rewriter.write(html.as_bytes())?;
-        rewriter.end()?;
+        rewriter.write(html.as_bytes()).map_err(SsgError::custom)?;
+        rewriter.end().map_err(SsgError::custom)?;
 
         // Convert output to string
-        let result = String::from_utf8(output)?;
+        let result = String::from_utf8(output).map_err(SsgError::custom)?;
         Ok(result)
     }
 
@@ -18,6 +18,7 @@ impl SsgAttribute {
     /// # Returns
     ///
     /// An `SsgAttribute` enum variant.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(attr: &str) -> Option<Self> {
         if attr == "data-ssg" {
             Some(SsgAttribute::Content)
@@ -0,0 +1,527 @@
+use crate::error::SsgError;
+use crate::processor::Processor;
+use lol_html::html_content::{ContentType, TextType};
+use lol_html::{
+    HtmlRewriter, Settings, doc_comments, doc_text, element, end_tag, rewrite_str, text,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Markers of yew's server-side rendering (`<!--<[App]>-->`, `<!--</#>-->`, ...), needed to
+/// hydrate the page
+const HYDRATION_MARKERS: [&str; 6] = ["<[", "</[", "<#", "</#", "<?", "</?"];
+
+/// Processor that minifies the generated HTML.
+///
+/// Whitespace runs outside `<pre>` and `<textarea>` collapse to a single space (whitespace
+/// between the elements of `<head>` is dropped), comments are removed except for conditional,
+/// license (`<!--! ... -->`, `@license`, `@preserve`) and yew hydration comments, inline CSS
+/// and JSON-LD are minified and repeated identical `<style>` blocks are dropped. Add it after
+/// the other processors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinifyProcessor {
+    /// Collapse whitespace in text
+    pub collapse_whitespace: bool,
+    /// Remove comments
+    pub remove_comments: bool,
+    /// Minify `<style>` blocks
+    pub minify_css: bool,
+    /// Minify `<script type="application/ld+json">` blocks
+    pub minify_json: bool,
+    /// Drop `<style>` blocks repeating an earlier one
+    pub dedupe_styles: bool,
+}
+
+impl Default for MinifyProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MinifyProcessor {
+    /// Creates a MinifyProcessor with every minification enabled
+    pub fn new() -> Self {
+        Self {
+            collapse_whitespace: true,
+            remove_comments: true,
+            minify_css: true,
+            minify_json: true,
+            dedupe_styles: true,
+        }
+    }
+
+    pub fn collapse_whitespace(mut self, enabled: bool) -> Self {
+        self.collapse_whitespace = enabled;
+        self
+    }
+
+    pub fn remove_comments(mut self, enabled: bool) -> Self {
+        self.remove_comments = enabled;
+        self
+    }
+
+    pub fn minify_css(mut self, enabled: bool) -> Self {
+        self.minify_css = enabled;
+        self
+    }
+
+    pub fn minify_json(mut self, enabled: bool) -> Self {
+        self.minify_json = enabled;
+        self
+    }
+
+    pub fn dedupe_styles(mut self, enabled: bool) -> Self {
+        self.dedupe_styles = enabled;
+        self
+    }
+
+    /// Indices of `<style>` blocks repeating an earlier block with the same attributes
+    fn duplicate_styles(html: &str) -> Result<HashSet<usize>, SsgError> {
+        let styles: RefCell<Vec<(String, String)>> = RefCell::new(Vec::new());
+
+        rewrite_str(
+            html,
+            lol_html::RewriteStrSettings {
+                element_content_handlers: vec![
+                    element!("style", |el| {
+                        let attributes: Vec<_> = el
+                            .attributes()
+                            .iter()
+                            .map(|attr| format!("{}={}", attr.name(), attr.value()))
+                            .collect();
+                        styles
+                            .borrow_mut()
+                            .push((attributes.join(" "), String::new()));
+                        Ok(())
+                    }),
+                    text!("style", |chunk| {
+                        if let Some((_, css)) = styles.borrow_mut().last_mut() {
+                            css.push_str(chunk.as_str());
+                        }
+                        Ok(())
+                    }),
+                ],
+                ..lol_html::RewriteStrSettings::new()
+            },
+        )
+        .map_err(SsgError::custom)?;
+
+        let mut seen = HashSet::new();
+        Ok(styles
+            .into_inner()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (attributes, css))| !seen.insert((attributes.clone(), minify_css(css))))
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
+
+impl Processor for MinifyProcessor {
+    fn name(&self) -> &'static str {
+        "minify_processor"
+    }
+
+    fn process(
+        &self,
+        html: &str,
+        _metadata: &HashMap<String, String>,
+        _generator_outputs: &HashMap<String, String>,
+        _content: &str,
+    ) -> Result<String, SsgError> {
+        let duplicates = if self.dedupe_styles {
+            Self::duplicate_styles(html)?
+        } else {
+            HashSet::new()
+        };
+
+        // Open `<pre>`/`<textarea>` elements and whether the text is inside `<head>`
+        let preserved = Rc::new(Cell::new(0usize));
+        let in_head = Rc::new(Cell::new(false));
+        let style_index = Cell::new(0usize);
+        // Text of the current `<style>` or JSON-LD block and whitespace state of the text node
+        let block = RefCell::new(String::new());
+        let after_space = Cell::new(false);
+
+        let mut element_handlers = Vec::new();
+        if self.dedupe_styles {
+            element_handlers.push(element!("style", |el| {
+                let index = style_index.get();
+                style_index.set(index + 1);
+                if duplicates.contains(&index) {
+                    el.remove();
+                }
+                Ok(())
+            }));
+        }
+        if self.collapse_whitespace {
+            element_handlers.push(element!("pre, textarea", |el| {
+                preserved.set(preserved.get() + 1);
+                let preserved = preserved.clone();
+                el.on_end_tag(end_tag!(move |_| {
+                    preserved.set(preserved.get().saturating_sub(1));
+                    Ok(())
+                }))
+            }));
+            element_handlers.push(element!("head", |el| {
+                in_head.set(true);
+                let in_head = in_head.clone();
+                el.on_end_tag(end_tag!(move |_| {
+                    in_head.set(false);
+                    Ok(())
+                }))
+            }));
+        }
+        if self.minify_css {
+            element_handlers.push(text!("style", |chunk| {
+                block.borrow_mut().push_str(chunk.as_str());
+                if chunk.last_in_text_node() {
+                    let css = minify_css(&block.take());
+                    chunk.replace(&css, ContentType::Html);
+                } else {
+                    chunk.remove();
+                }
+                Ok(())
+            }));
+        }
+        if self.minify_json {
+            element_handlers.push(text!(r#"script[type="application/ld+json"]"#, |chunk| {
+                block.borrow_mut().push_str(chunk.as_str());
+                if chunk.last_in_text_node() {
+                    let json = block.take();
+                    let json = minify_json(&json).unwrap_or(json);
+                    chunk.replace(&json, ContentType::Html);
+                } else {
+                    chunk.remove();
+                }
+                Ok(())
+            }));
+        }
+
+        let mut document_handlers = Vec::new();
+        if self.remove_comments {
+            document_handlers.push(doc_comments!(|comment| {
+                if !keep_comment(&comment.text()) {
+                    comment.remove();
+                }
+                Ok(())
+            }));
+        }
+        if self.collapse_whitespace {
+            document_handlers.push(doc_text!(|chunk| {
+                if chunk.text_type() != TextType::Data || preserved.get() > 0 {
+                    return Ok(());
+                }
+                let text = chunk.as_str();
+                let collapsed = if in_head.get() && text.trim().is_empty() {
+                    String::new()
+                } else {
+                    collapse_whitespace(text, &after_space)
+                };
+                if collapsed != text {
+                    chunk.replace(&collapsed, ContentType::Html);
+                }
+                if chunk.last_in_text_node() {
+                    after_space.set(false);
+                }
+                Ok(())
+            }));
+        }
+
+        let mut output = Vec::with_capacity(html.len());
+        let mut rewriter = HtmlRewriter::new(
+            Settings {
+                element_content_handlers: element_handlers,
+                document_content_handlers: document_handlers,
+                ..Settings::default()
+            },
+            |chunk: &[u8]| output.extend_from_slice(chunk),
+        );
+        rewriter
+            .write(html.as_bytes())
+            .and_then(|_| rewriter.end())
+            .map_err(SsgError::custom)?;
+
+        String::from_utf8(output).map_err(SsgError::custom)
+    }
+
+    fn clone_box(&self) -> Box<dyn Processor> {
+        Box::new(self.clone())
+    }
+}
+
+/// Whether a comment survives minification
+fn keep_comment(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with("[if")
+        || text.ends_with("<![endif]")
+        || text.starts_with('!')
+        || text.contains("@license")
+        || text.contains("@preserve")
+        || HYDRATION_MARKERS
+            .iter()
+            .any(|marker| text.starts_with(marker))
+}
+
+/// Collapse whitespace runs to a single space; `after_space` carries a trailing run over to
+/// the next chunk of the same text node
+fn collapse_whitespace(text: &str, after_space: &Cell<bool>) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            if !after_space.get() {
+                collapsed.push(' ');
+                after_space.set(true);
+            }
+        } else {
+            collapsed.push(c);
+            after_space.set(false);
+        }
+    }
+    collapsed
+}
+
+/// Remove comments (except `/*! ... */`) and insignificant whitespace from a stylesheet
+fn minify_css(css: &str) -> String {
+    let mut minified = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    let mut pending_space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut comment = String::from("/*");
+                let mut previous = '\0';
+                for c in chars.by_ref() {
+                    comment.push(c);
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                if comment.starts_with("/*!") {
+                    minified.push_str(&comment);
+                }
+            }
+            c if c.is_whitespace() => pending_space = true,
+            c => {
+                let last = minified.chars().last();
+                if pending_space
+                    && last.is_some_and(|last| !"{};,>:(".contains(last))
+                    && !"{};,>)".contains(c)
+                {
+                    minified.push(' ');
+                }
+                pending_space = false;
+
+                if c == '}' && last == Some(';') {
+                    minified.pop();
+                }
+                minified.push(c);
+
+                // Copy strings verbatim
+                if c == '"' || c == '\'' {
+                    let mut escaped = false;
+                    for s in chars.by_ref() {
+                        minified.push(s);
+                        if s == c && !escaped {
+                            break;
+                        }
+                        escaped = s == '\\' && !escaped;
+                    }
+                }
+            }
+        }
+    }
+
+    minified
+}
+
+/// Compact JSON without whitespace; `None` if `json` does not parse
+fn minify_json(json: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    // Keep `</script>` inside strings from closing the script element
+    Some(value.to_string().replace("</", "<\\/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Elements, attributes and text of a page, with whitespace runs collapsed and
+    /// whitespace-only text in `<head>` ignored. `<style>` elements (deduplicated by the
+    /// processor) and `<script>` contents are left out.
+    fn dom(html: &str) -> Vec<String> {
+        let nodes = Rc::new(RefCell::new(Vec::new()));
+        let raw_text = Rc::new(Cell::new(false));
+        let in_head = Rc::new(Cell::new(false));
+
+        rewrite_str(
+            html,
+            lol_html::RewriteStrSettings {
+                element_content_handlers: vec![element!("*", |el| {
+                    let tag = el.tag_name();
+                    let attributes: Vec<_> = el
+                        .attributes()
+                        .iter()
+                        .map(|attr| format!("{}={:?}", attr.name(), attr.value()))
+                        .collect();
+                    if tag != "style" {
+                        nodes
+                            .borrow_mut()
+                            .push(format!("<{} {}>", tag, attributes.join(" ")));
+                    }
+                    if !el.can_have_content() {
+                        return Ok(());
+                    }
+
+                    let flag = match tag.as_str() {
+                        "head" => Some(in_head.clone()),
+                        "style" | "script" => Some(raw_text.clone()),
+                        _ => None,
+                    };
+                    if let Some(flag) = &flag {
+                        flag.set(true);
+                    }
+                    let nodes = nodes.clone();
+                    el.on_end_tag(end_tag!(move |end| {
+                        if let Some(flag) = &flag {
+                            flag.set(false);
+                        }
+                        if end.name() != "style" {
+                            nodes.borrow_mut().push(format!("</{}>", end.name()));
+                        }
+                        Ok(())
+                    }))
+                })],
+                document_content_handlers: vec![doc_text!(|chunk| {
+                    let text = chunk.as_str();
+                    if raw_text.get()
+                        || text.is_empty()
+                        || (in_head.get() && text.trim().is_empty())
+                    {
+                        return Ok(());
+                    }
+                    let mut nodes = nodes.borrow_mut();
+                    // Text split by comments or chunk boundaries is one text node
+                    let text = match nodes.last() {
+                        Some(last) if last.starts_with('"') => {
+                            let last = nodes.pop().unwrap();
+                            format!("{}{}", &last[1..last.len() - 1], text)
+                        }
+                        _ => text.to_string(),
+                    };
+                    let text = collapse_whitespace(&text, &Cell::new(false));
+                    nodes.push(format!("\"{}\"", text));
+                    Ok(())
+                })],
+                ..lol_html::RewriteStrSettings::new()
+            },
+        )
+        .unwrap();
+
+        nodes.take()
+    }
+
+    fn minify(html: &str) -> String {
+        MinifyProcessor::new()
+            .process(html, &HashMap::new(), &HashMap::new(), "")
+            .unwrap()
+    }
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <!-- Template comment -->
+    <!--! Licensed under MIT -->
+    <title>  About   us </title>
+    <style media="screen">
+      /* Layout */
+      body  >  main { margin: 0 auto ; font-family: "Open  Sans", sans-serif; }
+      a :hover { color: red; }
+    </style>
+    <script type="application/ld+json">
+      { "@type": "Person", "name": "Ada  Lovelace", "url": "</script-free>" }
+    </script>
+    <style media="screen">
+      /* Layout */
+      body  >  main { margin: 0 auto ; font-family: "Open  Sans", sans-serif; }
+      a :hover { color: red; }
+    </style>
+  </head>
+  <body>
+    <!--[if IE]><p>Old browser</p><![endif]-->
+    <main><!--<[about_page::App]>-->
+      <h1>About   <em>us</em> &amp;  friends</h1>
+      <pre>  keep
+    this  </pre>
+      <textarea>  and   this </textarea>
+    <!--</[about_page::App]>--></main>
+  </body>
+</html>
+"#;
+
+    #[test]
+    fn test_minify_keeps_dom() {
+        let minified = minify(PAGE);
+
+        assert!(minified.len() < PAGE.len());
+        assert_eq!(dom(&minified), dom(PAGE));
+        // Idempotent, as processors run on the component markup and on the whole page
+        assert_eq!(minify(&minified), minified);
+
+        assert!(minified.contains("<head><!--! Licensed under MIT --><title>"));
+        assert!(!minified.contains("Template comment"));
+        assert!(minified.contains("<!--[if IE]><p>Old browser</p><![endif]-->"));
+        assert!(minified.contains("<!--<[about_page::App]>-->"));
+        assert!(minified.contains("<!--</[about_page::App]>-->"));
+        assert!(minified.contains("<h1>About <em>us</em> &amp; friends</h1>"));
+        assert!(minified.contains("<pre>  keep\n    this  </pre>"));
+        assert!(minified.contains("<textarea>  and   this </textarea>"));
+    }
+
+    #[test]
+    fn test_minify_inline_css_and_json_ld() {
+        let minified = minify(PAGE);
+
+        assert_eq!(minified.matches("<style").count(), 1);
+        assert!(minified.contains(
+            r#"<style media="screen">body>main{margin:0 auto;font-family:"Open  Sans",sans-serif}a :hover{color:red}</style>"#
+        ));
+
+        let start = minified
+            .find(r#"<script type="application/ld+json">"#)
+            .unwrap();
+        let json = &minified[start..];
+        let json = &json[json.find('>').unwrap() + 1..json.find("</script>").unwrap()];
+        assert_eq!(
+            json,
+            r#"{"@type":"Person","name":"Ada  Lovelace","url":"<\/script-free>"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(json).unwrap()["url"],
+            "</script-free>"
+        );
+    }
+
+    #[test]
+    fn test_minify_options() {
+        let processor = MinifyProcessor::new()
+            .remove_comments(false)
+            .collapse_whitespace(false);
+        let html = "<p>a  <!-- note -->  b</p>";
+
+        let result = processor
+            .process(html, &HashMap::new(), &HashMap::new(), "")
+            .unwrap();
+        assert_eq!(result, html);
+
+        let processor: MinifyProcessor = serde_yaml::from_str("dedupe_styles: false").unwrap();
+        assert!(!processor.dedupe_styles);
+        assert!(processor.minify_css);
+    }
+}
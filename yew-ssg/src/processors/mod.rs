@@ -1,7 +1,11 @@
+pub mod asset_processor;
 pub mod attribute_processor;
 pub mod generator_output_support;
+pub mod minify_processor;
 pub mod template_variable_processor;
 
+pub use asset_processor::AssetProcessor;
 pub use attribute_processor::AttributeProcessor;
 pub use generator_output_support::GeneratorOutputSupport;
+pub use minify_processor::MinifyProcessor;
 pub use template_variable_processor::TemplateVariableProcessor;
@@ -1,7 +1,7 @@
+use crate::error::SsgError;
 use crate::processor::Processor;
 use log::debug;
 use std::collections::HashMap;
-use std::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct TemplateVariableProcessor {
@@ -9,6 +9,12 @@ pub struct TemplateVariableProcessor {
     end_delimiter: String,
 }
 
+impl Default for TemplateVariableProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TemplateVariableProcessor {
     pub fn new() -> Self {
         Self {
@@ -35,7 +41,7 @@ impl Processor for TemplateVariableProcessor {
         metadata: &HashMap<String, String>,
         generator_outputs: &HashMap<String, String>,
         _content: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<String, SsgError> {
         let mut result = html.to_string();
         let mut replacements = Vec::new();
 
@@ -0,0 +1,238 @@
+//! Redirects for moved pages and route aliases.
+//!
+//! Every [`Redirect`] is written as a small HTML stub at the old path, with a meta refresh,
+//! a canonical link to the target and `noindex`, so it works on any static host. The same
+//! list can also be rendered as the redirect manifest of a specific host, which then answers
+//! with a real HTTP redirect instead.
+
+use crate::sitemap::escape_xml;
+use serde::{Deserialize, Serialize};
+
+/// A redirect from an old path to a new location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redirect {
+    /// Old path (e.g. `/old-about`)
+    pub from: String,
+    /// Target path or absolute URL
+    pub to: String,
+    /// HTTP status used in host manifests (default: 301)
+    #[serde(default = "default_status")]
+    pub status: u16,
+}
+
+impl Redirect {
+    /// Create a permanent redirect
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            status: default_status(),
+        }
+    }
+
+    /// Whether the target is an absolute URL rather than a site path
+    pub fn is_external(&self) -> bool {
+        self.to.contains("://")
+    }
+}
+
+/// Hosts whose redirect manifests can be generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedirectHost {
+    /// Netlify `_redirects`
+    Netlify,
+    /// Vercel `vercel.json`
+    Vercel,
+    /// nginx `map` snippet to include in the server configuration
+    Nginx,
+    /// Apache `.htaccess`
+    Apache,
+}
+
+impl RedirectHost {
+    /// File name of the manifest, relative to the output directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            RedirectHost::Netlify => "_redirects",
+            RedirectHost::Vercel => "vercel.json",
+            RedirectHost::Nginx => "redirects.nginx.conf",
+            RedirectHost::Apache => ".htaccess",
+        }
+    }
+}
+
+/// Redirect rules and the host manifests to generate from them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Redirects {
+    /// Redirects to write
+    #[serde(default)]
+    pub rules: Vec<Redirect>,
+    /// Host manifests written in addition to the HTML stubs
+    #[serde(default)]
+    pub hosts: Vec<RedirectHost>,
+}
+
+impl Redirects {
+    /// Returns true if there are no redirects
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Render the manifest of a host
+    pub fn render_host(&self, host: RedirectHost) -> String {
+        match host {
+            RedirectHost::Netlify => self
+                .rules
+                .iter()
+                .map(|rule| format!("{} {} {}\n", rule.from, rule.to, rule.status))
+                .collect(),
+            RedirectHost::Vercel => {
+                let redirects: Vec<_> = self
+                    .rules
+                    .iter()
+                    .map(|rule| {
+                        serde_json::json!({
+                            "source": rule.from,
+                            "destination": rule.to,
+                            "statusCode": rule.status,
+                        })
+                    })
+                    .collect();
+                let manifest = serde_json::json!({ "redirects": redirects });
+                serde_json::to_string_pretty(&manifest).unwrap_or_default() + "\n"
+            }
+            RedirectHost::Nginx => {
+                // A map only carries the target, so every redirect uses the status in `return`
+                let mut output = String::from(
+                    "# Include in the http block and add to the server block:\n\
+                     #   if ($redirect_uri) { return 301 $redirect_uri; }\n\
+                     map $uri $redirect_uri {\n",
+                );
+                for rule in &self.rules {
+                    output.push_str(&format!("    {} {};\n", rule.from, rule.to));
+                }
+                output.push_str("}\n");
+                output
+            }
+            RedirectHost::Apache => self
+                .rules
+                .iter()
+                .map(|rule| format!("Redirect {} {} {}\n", rule.status, rule.from, rule.to))
+                .collect(),
+        }
+    }
+}
+
+/// HTML stub sending visitors and crawlers from an old path to `target`
+pub fn stub_page(target: &str) -> String {
+    let target = escape_xml(target);
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Redirecting to {target}</title>\n\
+         <meta http-equiv=\"refresh\" content=\"0; url={target}\">\n\
+         <meta name=\"robots\" content=\"noindex\">\n\
+         <link rel=\"canonical\" href=\"{target}\">\n\
+         </head>\n\
+         <body>\n\
+         <p>This page has moved to <a href=\"{target}\">{target}</a>.</p>\n\
+         </body>\n\
+         </html>\n"
+    )
+}
+
+fn default_status() -> u16 {
+    301
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[test]
+    fn test_stub_page() {
+        let html = stub_page("https://example.com/about?a=1&b=2");
+        assert!(html.contains(
+            r#"<meta http-equiv="refresh" content="0; url=https://example.com/about?a=1&amp;b=2">"#
+        ));
+        assert!(
+            html.contains(r#"<link rel="canonical" href="https://example.com/about?a=1&amp;b=2">"#)
+        );
+        assert!(html.contains(r#"<meta name="robots" content="noindex">"#));
+    }
+
+    #[test]
+    fn test_host_manifests() {
+        let mut moved = Redirect::new("/blog", "/news");
+        moved.status = 302;
+        let redirects = Redirects {
+            rules: vec![Redirect::new("/old-about", "/about"), moved],
+            hosts: Vec::new(),
+        };
+
+        assert_eq!(
+            redirects.render_host(RedirectHost::Netlify),
+            "/old-about /about 301\n/blog /news 302\n"
+        );
+        assert_eq!(
+            redirects.render_host(RedirectHost::Apache),
+            "Redirect 301 /old-about /about\nRedirect 302 /blog /news\n"
+        );
+        assert!(
+            redirects.render_host(RedirectHost::Nginx).contains(
+                "map $uri $redirect_uri {\n    /old-about /about;\n    /blog /news;\n}\n"
+            )
+        );
+
+        let vercel: serde_json::Value =
+            serde_json::from_str(&redirects.render_host(RedirectHost::Vercel)).unwrap();
+        assert_eq!(vercel["redirects"][1]["source"], "/blog");
+        assert_eq!(vercel["redirects"][1]["statusCode"], 302);
+    }
+
+    #[tokio::test]
+    async fn test_generate_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com".to_string(),
+            )]))
+            .redirect(Redirect::new("/old-crate", "/crate/a"))
+            .redirect_host(RedirectHost::Netlify)
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let written = generator.generate_redirects().unwrap();
+        assert_eq!(written.len(), 2);
+        let stub = fs::read_to_string(dir.path().join("old-crate/index.html")).unwrap();
+        assert!(stub.contains(r#"<link rel="canonical" href="https://example.com/crate/a">"#));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("_redirects")).unwrap(),
+            "/old-crate /crate/a 301\n"
+        );
+
+        // A redirect must not replace a page written in the same run
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .redirect(Redirect::new("/", "/crate/a"))
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let err = generator.generate_redirects().unwrap_err();
+        assert!(err.to_string().contains("would overwrite"));
+    }
+}
@@ -0,0 +1,291 @@
+//! Structured results of a generation run.
+//!
+//! Every `generate*` method of the `StaticSiteGenerator` returns a [`GenerationReport`]
+//! describing the pages it produced. Reports of several runs can be merged and written
+//! to `build-report.json` to compare builds over time.
+
+use crate::error::{IoContext, SsgError};
+use crate::failure::PageFailure;
+use crate::precompress::CompressedFile;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name used by [`GenerationReport::save`]
+pub const REPORT_FILE_NAME: &str = "build-report.json";
+
+/// What happened to the output file of a page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageStatus {
+    /// The page was written to disk
+    Written,
+    /// The page matched the build manifest and was left untouched
+    Unchanged,
+}
+
+/// Report for a single generated page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageReport {
+    /// Route path of the page (including any path prefix)
+    pub route: String,
+    /// Output file, relative to the output directory
+    pub output_file: PathBuf,
+    /// Size of the generated HTML in bytes
+    pub bytes: usize,
+    /// Time spent rendering the component tree
+    #[serde(with = "duration_ms")]
+    pub render_time: Duration,
+    /// Time spent in generators, processors and the template
+    #[serde(with = "duration_ms")]
+    pub processing_time: Duration,
+    /// Whether the file was written or skipped
+    pub status: PageStatus,
+    /// Warnings raised while generating the page
+    pub warnings: Vec<String>,
+}
+
+/// Report of one or more generation runs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenerationReport {
+    /// Generated pages, sorted by route
+    pub pages: Vec<PageReport>,
+    /// Routes that failed to generate
+    #[serde(default)]
+    pub failures: Vec<PageFailure>,
+    /// Pre-compressed siblings written for the output files
+    #[serde(default)]
+    pub compressed: Vec<CompressedFile>,
+    /// Files of pages no longer in the site, removed by an incremental build (relative to the
+    /// output directory)
+    #[serde(default)]
+    pub removed: Vec<PathBuf>,
+    /// Wall-clock time of the run(s)
+    #[serde(with = "duration_ms")]
+    pub total_time: Duration,
+}
+
+impl GenerationReport {
+    /// Number of pages written to disk
+    pub fn written(&self) -> usize {
+        self.count(PageStatus::Written)
+    }
+
+    /// Number of pages skipped because they did not change
+    pub fn unchanged(&self) -> usize {
+        self.count(PageStatus::Unchanged)
+    }
+
+    /// Returns true if no route failed
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Return the report, or [`SsgError::PagesFailed`] if any route failed
+    pub fn into_result(self) -> Result<Self, SsgError> {
+        if self.is_success() {
+            Ok(self)
+        } else {
+            Err(SsgError::PagesFailed {
+                report: Box::new(self),
+            })
+        }
+    }
+
+    /// Total size of all generated pages in bytes
+    pub fn total_bytes(&self) -> usize {
+        self.pages.iter().map(|page| page.bytes).sum()
+    }
+
+    /// Bytes saved by the smallest compressed sibling of each compressed file
+    pub fn compression_savings(&self) -> u64 {
+        self.compressed.iter().map(CompressedFile::savings).sum()
+    }
+
+    /// Iterate over all warnings together with the route they belong to
+    pub fn warnings(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pages.iter().flat_map(|page| {
+            page.warnings
+                .iter()
+                .map(move |warning| (page.route.as_str(), warning.as_str()))
+        })
+    }
+
+    /// Find the report of a route
+    pub fn page(&self, route: &str) -> Option<&PageReport> {
+        self.pages.iter().find(|page| page.route == route)
+    }
+
+    /// Append the pages of another report
+    pub fn merge(&mut self, other: GenerationReport) {
+        self.pages.extend(other.pages);
+        self.failures.extend(other.failures);
+        self.compressed.extend(other.compressed);
+        self.removed.extend(other.removed);
+        self.total_time += other.total_time;
+    }
+
+    /// Write the report as pretty-printed JSON to the given file
+    pub fn write_json(&self, path: impl AsRef<Path>) -> Result<(), SsgError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_path(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).with_path(path)?;
+        Ok(())
+    }
+
+    /// Write the report to `build-report.json` inside the given directory
+    pub fn save(&self, output_dir: impl AsRef<Path>) -> Result<(), SsgError> {
+        self.write_json(output_dir.as_ref().join(REPORT_FILE_NAME))
+    }
+
+    fn count(&self, status: PageStatus) -> usize {
+        self.pages
+            .iter()
+            .filter(|page| page.status == status)
+            .count()
+    }
+}
+
+/// Serialize durations as fractional milliseconds
+mod duration_ms {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let millis = f64::deserialize(deserializer)?;
+        Ok(Duration::from_secs_f64(millis.max(0.0) / 1000.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+    use std::collections::HashMap;
+
+    fn page(route: &str, bytes: usize, status: PageStatus, warnings: &[&str]) -> PageReport {
+        PageReport {
+            route: route.to_string(),
+            output_file: PathBuf::from("index.html"),
+            bytes,
+            render_time: Duration::from_millis(2),
+            processing_time: Duration::from_millis(1),
+            status,
+            warnings: warnings.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_report_totals_and_merge() {
+        let mut report = GenerationReport {
+            pages: vec![page("/", 100, PageStatus::Written, &["No title"])],
+            failures: Vec::new(),
+            compressed: Vec::new(),
+            removed: Vec::new(),
+            total_time: Duration::from_millis(5),
+        };
+        report.merge(GenerationReport {
+            pages: vec![page("/about", 50, PageStatus::Unchanged, &[])],
+            failures: Vec::new(),
+            compressed: vec![CompressedFile {
+                path: PathBuf::from("index.html"),
+                bytes: 100,
+                gzip: Some(60),
+                brotli: Some(50),
+            }],
+            removed: vec![PathBuf::from("old/index.html")],
+            total_time: Duration::from_millis(3),
+        });
+
+        assert_eq!(report.pages.len(), 2);
+        assert_eq!(report.written(), 1);
+        assert_eq!(report.unchanged(), 1);
+        assert_eq!(report.total_bytes(), 150);
+        assert_eq!(report.total_time, Duration::from_millis(8));
+        assert_eq!(
+            report.warnings().collect::<Vec<_>>(),
+            vec![("/", "No title")]
+        );
+        assert_eq!(report.page("/about").unwrap().bytes, 50);
+        assert_eq!(report.compression_savings(), 50);
+        assert_eq!(report.removed, vec![PathBuf::from("old/index.html")]);
+    }
+
+    #[test]
+    fn test_report_save_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = GenerationReport {
+            pages: vec![page("/", 42, PageStatus::Written, &[])],
+            failures: Vec::new(),
+            compressed: Vec::new(),
+            removed: Vec::new(),
+            total_time: Duration::from_millis(7),
+        };
+        report.save(dir.path()).unwrap();
+
+        let json = fs::read_to_string(dir.path().join(REPORT_FILE_NAME)).unwrap();
+        assert!(json.contains("\"status\": \"written\""));
+        assert!(json.contains("\"render_time\": 2.0"));
+
+        let loaded: GenerationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.pages[0].bytes, 42);
+        assert_eq!(loaded.pages[0].render_time, Duration::from_millis(2));
+    }
+
+    #[tokio::test]
+    async fn test_generate_returns_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .without_default_generators()
+            .default_template_string("<title>{{ title }}</title>{{ content | safe }}".to_string())
+            .route_metadata(
+                "/crate/:id",
+                HashMap::from([("title".to_string(), "Crate".to_string())]),
+            )
+            .add_route_param("/crate/:id", "id", ["a", "b"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let mut report = generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        report.merge(
+            generator
+                .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+                .await
+                .unwrap(),
+        );
+
+        // `generate` also renders the default `Crate` variant at `/crate/`
+        assert_eq!(report.pages.len(), 4);
+        assert_eq!(report.written(), 4);
+
+        let home = report.page("/").unwrap();
+        assert_eq!(home.output_file, PathBuf::from("index.html"));
+        assert_eq!(
+            home.bytes,
+            fs::read_to_string(dir.path().join("index.html"))
+                .unwrap()
+                .len()
+        );
+        assert_eq!(home.warnings, vec!["No title provided for route '/'"]);
+
+        let crate_page = report.page("/crate/a").unwrap();
+        assert_eq!(crate_page.output_file, PathBuf::from("crate/a/index.html"));
+        assert!(crate_page.warnings.is_empty());
+
+        report.save(dir.path()).unwrap();
+        assert!(dir.path().join(crate::report::REPORT_FILE_NAME).exists());
+    }
+}
@@ -0,0 +1,190 @@
+//! Site-level `robots.txt` generation.
+//!
+//! Complements the per-page `<meta name="robots">` tags of the `RobotsMetaGenerator`
+//! with the crawl rules for the whole site, including a `Sitemap:` line pointing to the
+//! `sitemap.xml` written by the `StaticSiteGenerator`.
+
+use crate::sitemap::SITEMAP_FILE_NAME;
+use serde::{Deserialize, Serialize};
+
+/// File name of the generated robots file
+pub const ROBOTS_TXT_FILE_NAME: &str = "robots.txt";
+
+/// A `User-agent` group of `robots.txt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RobotsRule {
+    /// User agent the rule applies to (`*` for all crawlers)
+    pub user_agent: String,
+    /// Paths crawlers may visit
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Paths crawlers must not visit
+    #[serde(default)]
+    pub disallow: Vec<String>,
+    /// Seconds between requests (non-standard, honored by some crawlers)
+    #[serde(default)]
+    pub crawl_delay: Option<u32>,
+}
+
+impl RobotsRule {
+    /// Create an empty rule for the given user agent
+    pub fn new(user_agent: &str) -> Self {
+        Self {
+            user_agent: user_agent.to_string(),
+            allow: Vec::new(),
+            disallow: Vec::new(),
+            crawl_delay: None,
+        }
+    }
+
+    /// Allow a path
+    pub fn allow(mut self, path: &str) -> Self {
+        self.allow.push(path.to_string());
+        self
+    }
+
+    /// Disallow a path
+    pub fn disallow(mut self, path: &str) -> Self {
+        self.disallow.push(path.to_string());
+        self
+    }
+}
+
+/// Contents of `robots.txt`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RobotsTxt {
+    /// User agent groups; without rules every crawler is allowed everywhere
+    #[serde(default)]
+    pub rules: Vec<RobotsRule>,
+    /// Add a `Sitemap:` line derived from the site `domain`
+    #[serde(default = "default_sitemap")]
+    pub sitemap: bool,
+}
+
+impl Default for RobotsTxt {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            sitemap: default_sitemap(),
+        }
+    }
+}
+
+impl RobotsTxt {
+    /// Create a robots.txt allowing all crawlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a user agent group
+    pub fn rule(mut self, rule: RobotsRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Render the file; `domain` is used for the `Sitemap:` line when enabled
+    pub fn render(&self, domain: Option<&str>) -> String {
+        let mut output = String::new();
+
+        if self.rules.is_empty() {
+            output.push_str("User-agent: *\nAllow: /\n");
+        }
+        for (i, rule) in self.rules.iter().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            output.push_str(&format!("User-agent: {}\n", rule.user_agent));
+            for path in &rule.allow {
+                output.push_str(&format!("Allow: {}\n", path));
+            }
+            for path in &rule.disallow {
+                output.push_str(&format!("Disallow: {}\n", path));
+            }
+            if rule.allow.is_empty() && rule.disallow.is_empty() {
+                // An empty Disallow keeps the group valid and allows everything
+                output.push_str("Disallow:\n");
+            }
+            if let Some(delay) = rule.crawl_delay {
+                output.push_str(&format!("Crawl-delay: {}\n", delay));
+            }
+        }
+
+        if self.sitemap
+            && let Some(domain) = domain
+        {
+            output.push_str(&format!(
+                "\nSitemap: {}/{}\n",
+                domain.trim_end_matches('/'),
+                SITEMAP_FILE_NAME
+            ));
+        }
+
+        output
+    }
+}
+
+fn default_sitemap() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[test]
+    fn test_default_robots_txt() {
+        let robots = RobotsTxt::new();
+        assert_eq!(
+            robots.render(Some("https://example.com/")),
+            "User-agent: *\nAllow: /\n\nSitemap: https://example.com/sitemap.xml\n"
+        );
+        assert_eq!(robots.render(None), "User-agent: *\nAllow: /\n");
+    }
+
+    #[test]
+    fn test_robots_txt_rules() {
+        let mut robots = RobotsTxt::new()
+            .rule(RobotsRule::new("*").allow("/").disallow("/drafts/"))
+            .rule(RobotsRule::new("BadBot").disallow("/"));
+        robots.rules[0].crawl_delay = Some(5);
+        robots.sitemap = false;
+
+        assert_eq!(
+            robots.render(Some("https://example.com")),
+            "User-agent: *\nAllow: /\nDisallow: /drafts/\nCrawl-delay: 5\n\n\
+             User-agent: BadBot\nDisallow: /\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_site_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com".to_string(),
+            )]))
+            .robots_txt(RobotsTxt::new().rule(RobotsRule::new("*").disallow("/drafts/")))
+            .site_file(
+                ".well-known/security.txt",
+                "Contact: mailto:security@example.com",
+            )
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let written = generator.generate_site_files().unwrap();
+        assert_eq!(written.len(), 2);
+
+        let robots = fs::read_to_string(dir.path().join("robots.txt")).unwrap();
+        assert!(robots.contains("Disallow: /drafts/"));
+        assert!(robots.contains("Sitemap: https://example.com/sitemap.xml"));
+
+        let security = fs::read_to_string(dir.path().join(".well-known/security.txt")).unwrap();
+        assert_eq!(security, "Contact: mailto:security@example.com");
+    }
+}
@@ -0,0 +1,229 @@
+//! Data loaded for a route before it is rendered.
+//!
+//! A [`RouteDataProvider`] is registered for a route pattern (`/crate/:id`) and loads the
+//! data of every page matching it, e.g. from a file, a database or an API. The data is
+//! serialized to JSON and handed to the components through the render context, where
+//! `yew_router::use_route_data::<T>()` deserializes it. The JSON is also embedded in the page
+//! (see `yew_router::ROUTE_DATA_ELEMENT_ID`), so the client can reuse it after loading the page.
+//!
+//! ```no_run
+//! use yew_ssg::prelude::*;
+//!
+//! let config = SsgConfigBuilder::new()
+//!     // Reads `content/crates/yew-ssg.yaml` for `/crate/yew-ssg`
+//!     .route_data("/crate/:id", FileData::new("content/crates/{id}.yaml"))
+//!     // Any async closure works as well
+//!     .route_data("/stats", |_context: SsgRenderContext| async {
+//!         Ok(serde_json::json!({ "downloads": 1200 }))
+//!     })
+//!     .build();
+//! ```
+
+use crate::error::{IoContext, SsgError};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use yew_router::SsgRenderContext;
+
+/// Loads the data of the pages matching a route pattern
+pub trait RouteDataProvider: Send + Sync {
+    /// Load the data of a page; the context holds its path, parameters, language and metadata
+    fn load<'a>(&'a self, context: &'a SsgRenderContext) -> BoxFuture<'a, Result<Value, SsgError>>;
+
+    /// Directory the data is read from, watched by [`SiteWatcher`](crate::watch::SiteWatcher)
+    fn source_dir(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+impl<F, Fut> RouteDataProvider for F
+where
+    F: Fn(SsgRenderContext) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Value, SsgError>> + Send + 'static,
+{
+    fn load<'a>(&'a self, context: &'a SsgRenderContext) -> BoxFuture<'a, Result<Value, SsgError>> {
+        Box::pin(self(context.clone()))
+    }
+}
+
+/// A provider registered for a route pattern
+#[derive(Clone)]
+pub struct RouteData {
+    /// Route pattern the provider loads data for (e.g. `/crate/:id`)
+    pub pattern: String,
+    /// The provider
+    pub provider: Arc<dyn RouteDataProvider>,
+}
+
+impl RouteData {
+    /// Register a provider for a route pattern
+    pub fn new(pattern: &str, provider: impl RouteDataProvider + 'static) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            provider: Arc::new(provider),
+        }
+    }
+}
+
+impl fmt::Debug for RouteData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteData")
+            .field("pattern", &self.pattern)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Provider reading a JSON or YAML file per page.
+///
+/// `{name}` placeholders in the path are replaced with the route parameters of the page,
+/// `{lang}` with its language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileData {
+    /// Path of the file, e.g. `content/crates/{id}.json`
+    pub file: String,
+}
+
+impl FileData {
+    /// Read the file at `file` (with placeholders replaced)
+    pub fn new(file: &str) -> Self {
+        Self {
+            file: file.to_string(),
+        }
+    }
+
+    /// File of a page
+    pub fn path(&self, context: &SsgRenderContext) -> PathBuf {
+        let mut file = self.file.clone();
+        for (name, value) in &context.params {
+            file = file.replace(&format!("{{{}}}", name), value);
+        }
+        if let Some(lang) = &context.lang {
+            file = file.replace("{lang}", lang);
+        }
+        PathBuf::from(file)
+    }
+}
+
+impl RouteDataProvider for FileData {
+    fn load<'a>(&'a self, context: &'a SsgRenderContext) -> BoxFuture<'a, Result<Value, SsgError>> {
+        Box::pin(async move {
+            let path = self.path(context);
+            let source = fs::read_to_string(&path).with_path(&path)?;
+            let is_yaml = path
+                .extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml");
+            if is_yaml {
+                serde_yaml::from_str(&source).map_err(SsgError::custom)
+            } else {
+                serde_json::from_str(&source).map_err(SsgError::custom)
+            }
+        })
+    }
+
+    /// Directory above the first placeholder; `None` if the path starts with one
+    fn source_dir(&self) -> Option<PathBuf> {
+        let fixed = self.file.split('{').next().unwrap_or_default();
+        fixed
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::failure::{BuildStage, FailurePolicy};
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::ContextRoute;
+    use std::collections::HashMap;
+    use yew::prelude::*;
+
+    #[test]
+    fn test_file_data() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("yew-ssg.yaml"),
+            "name: yew-ssg\nstars: 12\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("de.json"), r#"{"greeting": "Hallo"}"#).unwrap();
+
+        let context = SsgRenderContext::new("/crate/yew-ssg")
+            .with_params(HashMap::from([("id".to_string(), "yew-ssg".to_string())]))
+            .with_lang(Some("de".to_string()));
+        let load = |file: &str| {
+            let provider = FileData::new(&format!("{}/{}", dir.path().display(), file));
+            futures::executor::block_on(provider.load(&context))
+        };
+
+        assert_eq!(
+            load("{id}.yaml").unwrap(),
+            serde_json::json!({ "name": "yew-ssg", "stars": 12 })
+        );
+        assert_eq!(load("{lang}.json").unwrap()["greeting"], "Hallo");
+        assert!(matches!(load("missing.json"), Err(SsgError::Io { .. })));
+
+        assert_eq!(
+            FileData::new("content/crates/{id}.json").source_dir(),
+            Some(PathBuf::from("content/crates"))
+        );
+        assert_eq!(FileData::new("{id}.json").source_dir(), None);
+    }
+
+    #[function_component(RouteDataProbe)]
+    fn route_data_probe() -> Html {
+        let stars = yew_router::use_route_data::<HashMap<String, u32>>()
+            .and_then(|data| data.get("stars").copied());
+        html! { <p>{format!("stars={:?}", stars)}</p> }
+    }
+
+    #[tokio::test]
+    async fn test_route_data_is_passed_and_embedded() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .default_template_string("{{ content | safe }}".to_string())
+            .add_route_param("/crate/:id", "id", ["yew-ssg", "broken"])
+            .route_data("/crate/:id", |context: SsgRenderContext| async move {
+                match context.param("id") {
+                    Some("broken") => Err(SsgError::custom("no such crate")),
+                    _ => Ok(serde_json::json!({ "stars": 12 })),
+                }
+            })
+            .failure_policy(FailurePolicy::SkipRoute)
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let report = generator
+            .generate_parameterized_routes::<ContextRoute, RouteDataProbe>()
+            .await
+            .unwrap();
+        generator
+            .generate::<ContextRoute, RouteDataProbe>()
+            .await
+            .unwrap();
+
+        let page = fs::read_to_string(dir.path().join("crate/yew-ssg/index.html")).unwrap();
+        assert!(page.contains("stars=Some(12)"));
+        assert!(page.contains(
+            r#"<script type="application/json" id="yew-ssg-route-data">{"stars":12}</script>"#
+        ));
+
+        // Routes without a provider get no data
+        let home = fs::read_to_string(dir.path().join("index.html")).unwrap();
+        assert!(home.contains("stars=None"));
+        assert!(!home.contains(yew_router::ROUTE_DATA_ELEMENT_ID));
+
+        let failure = &report.failures[0];
+        assert_eq!(failure.route, "/crate/broken");
+        assert_eq!(failure.stage, BuildStage::Data);
+    }
+}
@@ -0,0 +1,349 @@
+//! `sitemap.xml` generation.
+//!
+//! The sitemap lists the canonical URL of every generated page together with its
+//! `hreflang` alternates. URLs are taken from the [`CanonicalLinkGenerator`], so the
+//! sitemap always agrees with the `<link rel="canonical">` and `<link rel="alternate">`
+//! tags written into the pages themselves.
+//!
+//! Route metadata used:
+//! - `date_modified` → `<lastmod>`
+//! - `changefreq` → `<changefreq>`
+//! - `priority` → `<priority>`
+//! - `robots` containing `noindex` → the page is left out
+
+use crate::error::{IoContext, SsgError};
+use crate::generators::{self, CanonicalLinkGenerator};
+use log::{info, warn};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the sitemap (or of the sitemap index for large sites)
+pub const SITEMAP_FILE_NAME: &str = "sitemap.xml";
+
+/// Maximum number of URLs a single sitemap file may contain
+pub const MAX_URLS_PER_SITEMAP: usize = 50_000;
+
+/// A single `<url>` entry of the sitemap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SitemapUrl {
+    /// Absolute canonical URL of the page
+    pub loc: String,
+    /// Last modification date (W3C datetime, e.g. `2025-03-10`)
+    pub lastmod: Option<String>,
+    /// Expected change frequency (`daily`, `weekly`, ...)
+    pub changefreq: Option<String>,
+    /// Priority relative to the other pages of the site (`0.0` - `1.0`)
+    pub priority: Option<String>,
+    /// Alternate language versions as `(hreflang, url)` pairs
+    pub alternates: Vec<(String, String)>,
+}
+
+impl SitemapUrl {
+    /// Build the entry for a page from its metadata.
+    ///
+    /// Returns `None` for pages marked `noindex` and for pages without a canonical URL.
+    pub fn from_metadata(
+        canonical: &CanonicalLinkGenerator,
+        metadata: &HashMap<String, String>,
+    ) -> Option<Self> {
+        if is_noindex(metadata) {
+            return None;
+        }
+
+        let loc = canonical.canonical_url(metadata)?;
+        Some(Self {
+            loc,
+            lastmod: metadata.get("date_modified").cloned(),
+            changefreq: metadata.get("changefreq").cloned(),
+            priority: metadata.get("priority").cloned(),
+            alternates: canonical.alternate_urls(metadata),
+        })
+    }
+
+    fn render(&self, xml: &mut String) {
+        xml.push_str("  <url>\n");
+        xml.push_str(&format!("    <loc>{}</loc>\n", escape_xml(&self.loc)));
+        if let Some(lastmod) = &self.lastmod {
+            xml.push_str(&format!("    <lastmod>{}</lastmod>\n", escape_xml(lastmod)));
+        }
+        if let Some(changefreq) = &self.changefreq {
+            xml.push_str(&format!(
+                "    <changefreq>{}</changefreq>\n",
+                escape_xml(changefreq)
+            ));
+        }
+        if let Some(priority) = &self.priority {
+            xml.push_str(&format!(
+                "    <priority>{}</priority>\n",
+                escape_xml(priority)
+            ));
+        }
+        for (lang, href) in &self.alternates {
+            xml.push_str(&format!(
+                "    <xhtml:link rel=\"alternate\" hreflang=\"{}\" href=\"{}\"/>\n",
+                escape_xml(lang),
+                escape_xml(href)
+            ));
+        }
+        xml.push_str("  </url>\n");
+    }
+}
+
+/// Collection of sitemap entries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sitemap {
+    /// Entries, sorted by URL when rendered
+    pub urls: Vec<SitemapUrl>,
+}
+
+impl Sitemap {
+    /// Create an empty sitemap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a sitemap from the metadata of all generated pages.
+    ///
+    /// Pages sharing a canonical URL (e.g. a translation pointing to the default
+    /// language) are listed once.
+    pub fn from_pages<'a, I>(canonical: &CanonicalLinkGenerator, pages: I) -> Self
+    where
+        I: IntoIterator<Item = &'a HashMap<String, String>>,
+    {
+        let mut sitemap = Self::new();
+        let mut seen = HashSet::new();
+        for metadata in pages {
+            match SitemapUrl::from_metadata(canonical, metadata) {
+                Some(url) if seen.insert(url.loc.clone()) => sitemap.push(url),
+                Some(_) => {}
+                None if !is_noindex(metadata) => warn!(
+                    "No canonical URL for {:?}, leaving it out of the sitemap (is `domain` set?)",
+                    metadata.get("path")
+                ),
+                None => {}
+            }
+        }
+        sitemap
+    }
+
+    /// Add an entry
+    pub fn push(&mut self, url: SitemapUrl) {
+        self.urls.push(url);
+    }
+
+    /// Number of listed URLs
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    /// Returns true if the sitemap lists no URLs
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// Render the entries as `<urlset>` documents of at most [`MAX_URLS_PER_SITEMAP`] URLs each
+    pub fn render(&self) -> Vec<String> {
+        let mut urls: Vec<&SitemapUrl> = self.urls.iter().collect();
+        urls.sort_by(|a, b| a.loc.cmp(&b.loc));
+
+        if urls.is_empty() {
+            return vec![render_urlset(&[])];
+        }
+        urls.chunks(MAX_URLS_PER_SITEMAP)
+            .map(render_urlset)
+            .collect()
+    }
+
+    /// Write `sitemap.xml` to the output directory.
+    ///
+    /// Sites with more than [`MAX_URLS_PER_SITEMAP`] URLs are split into `sitemap-1.xml`,
+    /// `sitemap-2.xml`, ... and `sitemap.xml` becomes a sitemap index pointing to them
+    /// below `base_url`. Returns the written files.
+    pub fn write(
+        &self,
+        output_dir: impl AsRef<Path>,
+        base_url: &str,
+    ) -> Result<Vec<PathBuf>, SsgError> {
+        let output_dir = output_dir.as_ref();
+        fs::create_dir_all(output_dir).with_path(output_dir)?;
+
+        let mut documents = self.render();
+        if documents.len() == 1 {
+            let path = output_dir.join(SITEMAP_FILE_NAME);
+            fs::write(&path, documents.remove(0)).with_path(&path)?;
+            info!("Wrote sitemap with {} URLs to {:?}", self.len(), path);
+            return Ok(vec![path]);
+        }
+
+        let base_url = base_url.trim_end_matches('/');
+        let mut written = Vec::new();
+        let mut index = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for (i, document) in documents.into_iter().enumerate() {
+            let file_name = format!("sitemap-{}.xml", i + 1);
+            let path = output_dir.join(&file_name);
+            fs::write(&path, document).with_path(&path)?;
+            written.push(path);
+
+            index.push_str(&format!(
+                "  <sitemap>\n    <loc>{}</loc>\n  </sitemap>\n",
+                escape_xml(&format!("{}/{}", base_url, file_name))
+            ));
+        }
+        index.push_str("</sitemapindex>\n");
+
+        let index_path = output_dir.join(SITEMAP_FILE_NAME);
+        fs::write(&index_path, index).with_path(&index_path)?;
+        info!(
+            "Wrote sitemap index for {} URLs in {} files to {:?}",
+            self.len(),
+            written.len(),
+            index_path
+        );
+        written.insert(0, index_path);
+        Ok(written)
+    }
+}
+
+/// Whether the page's robots metadata keeps it out of search indexes
+fn is_noindex(metadata: &HashMap<String, String>) -> bool {
+    metadata
+        .get("robots")
+        .is_some_and(|robots| generators::is_noindex(robots))
+}
+
+fn render_urlset(urls: &[&SitemapUrl]) -> String {
+    let with_alternates = urls.iter().any(|url| !url.alternates.is_empty());
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"");
+    if with_alternates {
+        xml.push_str(" xmlns:xhtml=\"http://www.w3.org/1999/xhtml\"");
+    }
+    xml.push_str(">\n");
+
+    for url in urls {
+        url.render(&mut xml);
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+pub(crate) fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::ContextRoute;
+
+    fn metadata(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sitemap_entries_from_metadata() {
+        let canonical = CanonicalLinkGenerator::with_domain("https://example.com");
+        let pages = [
+            metadata(&[
+                ("path", "/about"),
+                ("date_modified", "2025-03-10"),
+                ("changefreq", "monthly"),
+                ("priority", "0.8"),
+                ("alternate_languages", "en,de"),
+            ]),
+            metadata(&[("path", "/404"), ("robots", "noindex, nofollow")]),
+            metadata(&[("path", "/")]),
+            metadata(&[("path", "/en/"), ("canonical", "https://example.com/")]),
+        ];
+
+        let sitemap = Sitemap::from_pages(&canonical, &pages);
+        assert_eq!(sitemap.len(), 2);
+
+        let xml = &sitemap.render()[0];
+        assert!(xml.contains("xmlns:xhtml=\"http://www.w3.org/1999/xhtml\""));
+        assert!(xml.contains("<loc>https://example.com/</loc>"));
+        assert!(xml.contains("<loc>https://example.com/about</loc>"));
+        assert!(xml.contains("<lastmod>2025-03-10</lastmod>"));
+        assert!(xml.contains("<changefreq>monthly</changefreq>"));
+        assert!(xml.contains("<priority>0.8</priority>"));
+        assert!(xml.contains(
+            "<xhtml:link rel=\"alternate\" hreflang=\"de\" href=\"https://example.com/de/about\"/>"
+        ));
+        assert!(xml.contains(
+            "<xhtml:link rel=\"alternate\" hreflang=\"x-default\" href=\"https://example.com/about\"/>"
+        ));
+        assert!(!xml.contains("/404"));
+        assert!(xml.find("https://example.com/<").unwrap() < xml.find("/about<").unwrap());
+    }
+
+    #[test]
+    fn test_sitemap_index_for_large_sites() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sitemap = Sitemap::new();
+        for i in 0..MAX_URLS_PER_SITEMAP + 1 {
+            sitemap.push(SitemapUrl {
+                loc: format!("https://example.com/page/{:06}", i),
+                lastmod: None,
+                changefreq: None,
+                priority: None,
+                alternates: Vec::new(),
+            });
+        }
+
+        let written = sitemap.write(dir.path(), "https://example.com/").unwrap();
+        assert_eq!(written.len(), 3);
+
+        let index = fs::read_to_string(dir.path().join(SITEMAP_FILE_NAME)).unwrap();
+        assert!(index.contains("<sitemapindex"));
+        assert!(index.contains("<loc>https://example.com/sitemap-1.xml</loc>"));
+        assert!(index.contains("<loc>https://example.com/sitemap-2.xml</loc>"));
+
+        let second = fs::read_to_string(dir.path().join("sitemap-2.xml")).unwrap();
+        assert_eq!(second.matches("<url>").count(), 1);
+        assert!(!second.contains("xmlns:xhtml"));
+    }
+
+    #[test]
+    fn test_generate_sitemap() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com".to_string(),
+            )]))
+            .route_metadata(
+                "/",
+                HashMap::from([("date_modified".to_string(), "2025-03-10".to_string())]),
+            )
+            .route_metadata(
+                "/crate/",
+                HashMap::from([("robots".to_string(), "noindex".to_string())]),
+            )
+            .add_route_param("/crate/:id", "id", ["a"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let written = generator.generate_sitemap::<ContextRoute>().unwrap();
+        assert_eq!(written, vec![dir.path().join("sitemap.xml")]);
+
+        let xml = fs::read_to_string(dir.path().join("sitemap.xml")).unwrap();
+        assert!(xml.contains("<loc>https://example.com/</loc>"));
+        assert!(xml.contains("<lastmod>2025-03-10</lastmod>"));
+        assert!(!xml.contains("/crate/"));
+    }
+}
@@ -1,15 +1,51 @@
+use crate::assets::AssetManifest;
+use crate::build_manifest::{BuildManifest, page_hash};
+use crate::build_plan::{BuildPlan, PlanIssue, PlannedPage};
 use crate::config::SsgConfig;
+use crate::dev_server::{self, DevSite, LIVE_RELOAD_PATH};
+use crate::error::{IoContext, SsgError};
+use crate::failure::{FailurePolicy, PageFailure};
+use crate::feeds::{Feed, FeedItem};
+use crate::generators;
+use crate::link_check::LinkReport;
+use crate::paths::{match_pattern, strip_lang_prefix};
+use crate::plugin::GeneratedPage;
+use crate::precompress::CompressedFile;
+use crate::processor::Processor;
+use crate::processors::AssetProcessor;
+use crate::redirects::{Redirect, Redirects, stub_page};
+use crate::report::{GenerationReport, PageReport, PageStatus};
+use crate::robots_txt::ROBOTS_TXT_FILE_NAME;
+use crate::sitemap::Sitemap;
+use crate::template_context::PageInfo;
+use crate::template_functions::register_builtins;
+use futures::FutureExt;
+use futures::stream::{self, StreamExt};
 use log::{info, warn};
-use minijinja::Environment;
-use std::collections::HashMap;
-use std::error::Error;
+use minijinja::{Environment, Value};
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::fs;
-use std::path::PathBuf;
+use std::net::{TcpListener, ToSocketAddrs};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use strum::IntoEnumIterator;
 use yew::ServerRenderer;
 use yew::prelude::*;
-use yew_router::Routable;
+use yew_router::{
+    OutputLayout, Routable, SsgRenderContext, SsgRoot, SsgRootProps, context_script,
+    route_data_script,
+};
+
+/// Name of the layout used by pages that do not select one
+const BASE_TEMPLATE: &str = "base";
+
+/// File name of not-found pages, picked up by GitHub Pages, Netlify and S3
+const NOT_FOUND_FILE_NAME: &str = "404.html";
 
 const DEFAULT_TEMPLATE: &str = r#"<!DOCTYPE html>
 <html lang="en">
@@ -21,8 +57,9 @@ const DEFAULT_TEMPLATE: &str = r#"<!DOCTYPE html>
         {{ open_graph | default("") | safe }}
         {{ twitter_card | default("") | safe }}
         {{ robots_meta | default("") | safe }}
-        <link rel="stylesheet" href="/styles.css">
-        <script defer src="/app.js"></script>
+        {{ feed_links | default("") | safe }}
+        <link rel="stylesheet" href="{{ asset_url("/styles.css") }}">
+        <script defer src="{{ asset_url("/app.js") }}"></script>
     </head>
     <body>
         <div id="app">{{ content | safe }}</div>
@@ -36,21 +73,61 @@ pub struct StaticSiteGenerator {
     pub config: SsgConfig,
     /// Template environment.
     pub template_env: Environment<'static>,
+    /// Output files written during the current run, mapped to the route that wrote them.
+    written_outputs: Mutex<HashMap<PathBuf, String>>,
+    /// Pages generated by the running [`Self::build`], handed to the plugins' `after_build`
+    /// hooks. `None` outside of a build.
+    generated_pages: Mutex<Option<Vec<GeneratedPage>>>,
+    /// Component markup shared with later generators, so they re-wrap pages without rendering.
+    markup_cache: Option<MarkupCache>,
+    /// Rewrites asset references to their output paths, if an asset pipeline is configured.
+    asset_processor: Option<AssetProcessor>,
 }
 
+/// Component markup by render context, see [`markup_key`].
+pub(crate) type MarkupCache = Arc<Mutex<HashMap<String, String>>>;
+
 impl StaticSiteGenerator {
     /// Create a new static site generator from the provided configuration.
-    pub fn new(config: SsgConfig) -> Result<Self, Box<dyn Error>> {
+    pub fn new(config: SsgConfig) -> Result<Self, SsgError> {
         let mut env = Environment::new();
         let mut template_loaded = false;
 
+        // Output paths of the static assets, read from the source directory
+        let asset_processor = match &config.assets {
+            Some(pipeline) => Some(
+                AssetProcessor::new(AssetManifest::scan(pipeline)?)
+                    .with_base_path(&config.path_prefix)
+                    .with_domain(config.site_domain().as_deref()),
+            ),
+            None => None,
+        };
+        register_builtins(
+            &mut env,
+            &config,
+            asset_processor.as_ref().map(AssetProcessor::manifest),
+        );
+        config.template_functions.register(&mut env);
+
+        // Named layouts and partials, available to includes and extends
+        if let Some(dir) = &config.templates_dir {
+            load_templates_dir(&mut env, dir)?;
+        }
+        for (name, source) in &config.templates {
+            env.add_template_owned(name.clone(), source.clone())
+                .map_err(template_load_error)?;
+        }
+
         // 1. Try loading from template_path
         if let Some(path) = &config.template_path {
             if path.exists() {
                 match fs::read_to_string(path) {
                     Ok(content) => {
-                        let static_template = Box::leak(content.into_boxed_str());
-                        env.add_template("base", static_template)?;
+                        env.add_template_owned(BASE_TEMPLATE, content)
+                            .map_err(|source| SsgError::TemplateLoad {
+                                path: Some(path.clone()),
+                                source: Box::new(source),
+                            })?;
                         template_loaded = true;
                     }
                     Err(e) => warn!("Failed to read template: {}", e),
@@ -62,14 +139,20 @@ impl StaticSiteGenerator {
 
         // 2. Try using default_template string from config
         if !template_loaded && !config.default_template.is_empty() {
-            let static_template = Box::leak(config.default_template.clone().into_boxed_str());
-            env.add_template("base", static_template)?;
+            env.add_template_owned(BASE_TEMPLATE, config.default_template.clone())
+                .map_err(template_load_error)?;
             template_loaded = true;
         }
 
-        // 3. Fallback to built-in default template
+        // 3. A `base.html` layout from the templates directory
+        if !template_loaded {
+            template_loaded = env.get_template(&format!("{}.html", BASE_TEMPLATE)).is_ok();
+        }
+
+        // 4. Fallback to built-in default template
         if !template_loaded {
-            env.add_template("base", DEFAULT_TEMPLATE)?;
+            env.add_template(BASE_TEMPLATE, DEFAULT_TEMPLATE)
+                .map_err(template_load_error)?;
             info!("Using built-in default HTML template");
         }
 
@@ -78,18 +161,29 @@ impl StaticSiteGenerator {
         Ok(Self {
             config,
             template_env: env,
+            written_outputs: Mutex::new(HashMap::new()),
+            generated_pages: Mutex::new(None),
+            markup_cache: None,
+            asset_processor,
         })
     }
 
+    /// Reuse the component markup of earlier generators sharing `cache` and add to it.
+    ///
+    /// Pages whose render context (path, language and output layout) is in the cache are not
+    /// rendered again; only generators, processors and templates run for them.
+    pub(crate) fn with_markup_cache(mut self, cache: MarkupCache) -> Self {
+        self.markup_cache = Some(cache);
+        self
+    }
+
+    /// Set the fallback language for code running outside of a component tree
     pub fn set_current_language(lang: &str) {
-        // Set both environment variable and thread-local
-        unsafe { std::env::set_var("YEW_SSG_CURRENT_LANG", lang) };
         yew_router::LanguageContext::set_thread_local_lang(lang);
     }
 
-    /// Clear the current language from both sources
+    /// Clear the fallback language
     pub fn clear_current_language() {
-        unsafe { std::env::remove_var("YEW_SSG_CURRENT_LANG") };
         yew_router::LanguageContext::clear_thread_local_lang();
     }
 
@@ -98,134 +192,577 @@ impl StaticSiteGenerator {
         yew_router::LanguageContext::get_current_lang()
     }
 
+    /// Generate the whole site: [`Self::copy_assets`], [`Self::generate`] and
+    /// [`Self::generate_parameterized_routes`], with the `before_build` and `after_build` hooks
+    /// of the configured plugins around them.
+    ///
+    /// Under [`FailurePolicy::ContinueAndReport`] the parameterized routes are still generated
+    /// and the plugins still run when routes failed; the failures are returned afterwards.
+    pub async fn build<R, C>(&self) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        for plugin in &self.config.plugins {
+            plugin
+                .before_build(&self.config)
+                .map_err(|error| error.in_plugin(None, plugin.name()))?;
+        }
+        self.copy_assets()?;
+
+        // Pages are only kept while the build runs, even if it fails
+        self.swap_generated_pages(Some(Vec::new()))?;
+        let report = self.render_site::<R, C>().await;
+        let mut pages = self.swap_generated_pages(None)?.unwrap_or_default();
+        let mut report = report?;
+        if self.config.incremental {
+            report.removed = self.remove_stale_pages::<R>()?;
+        }
+
+        pages.sort_by(|a, b| a.page.url.cmp(&b.page.url));
+        for plugin in &self.config.plugins {
+            plugin
+                .after_build(&self.config, &pages)
+                .map_err(|error| error.in_plugin(None, plugin.name()))?;
+        }
+
+        if self.config.failure_policy == FailurePolicy::ContinueAndReport {
+            report.into_result()
+        } else {
+            Ok(report)
+        }
+    }
+
+    /// Render the pages of [`Self::generate`] and [`Self::generate_parameterized_routes`]
+    /// in one run.
+    async fn render_site<R, C>(&self) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        self.start_run()?;
+        let routes = self.site_routes::<R>(&self.path_prefix());
+        let mut report = self
+            .render_routes::<R, C>(&routes)
+            .await
+            .or_else(SsgError::into_report)?;
+        report.merge(
+            self.render_parameterized_routes::<R, C>(Some(&routes))
+                .await
+                .or_else(SsgError::into_report)?,
+        );
+        Ok(report)
+    }
+
+    /// Serve the site at `addr` (e.g. `127.0.0.1:8080`), rendering every page on request.
+    ///
+    /// Requested paths are recognized with `R::recognize` and rendered through the same
+    /// generators, processors and templates as a build, without writing anything. Other files
+    /// (e.g. the `trunk` output) are served from the output directory. Pages get a live-reload
+    /// snippet that reloads them when a template, asset, content or output file changes or the
+    /// server restarts. Templates are loaded and routes planned again after such a change;
+    /// configuration changes need a restart.
+    ///
+    /// Runs until the process is stopped.
+    pub async fn serve<R, C>(&self, addr: impl ToSocketAddrs) -> Result<(), SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        let listener = TcpListener::bind(addr)
+            .map_err(|e| SsgError::custom(format!("Failed to start the dev server: {}", e)))?;
+        if let Ok(address) = listener.local_addr() {
+            info!("🌐 Serving on http://{}", address);
+        }
+
+        let mut site = DevSite::new(self);
+        let mut connections = dev_server::incoming(listener);
+        while let Some((mut stream, request)) = connections.next().await {
+            let response = site.respond::<R, C>(&request).await;
+            if request.path != LIVE_RELOAD_PATH {
+                info!("{} {} -> {}", request.method, request.path, response.status);
+            }
+            if let Err(e) = response.write_to(&mut stream, request.method == "HEAD") {
+                warn!("Failed to answer {}: {}", request.path, e);
+            }
+        }
+        Ok(())
+    }
+
     /// Generate static HTML files for all routes.
-    pub async fn generate<R, C>(&self) -> Result<(), Box<dyn Error>>
+    pub async fn generate<R, C>(&self) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        self.start_run()?;
+        let routes = self.site_routes::<R>(&self.path_prefix());
+        self.render_routes::<R, C>(&routes).await
+    }
+
+    /// Render the pages of [`Self::generate`] within the current run, listing `routes` to
+    /// the templates.
+    async fn render_routes<R, C>(&self, routes: &Value) -> Result<GenerationReport, SsgError>
     where
         R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
         C: BaseComponent<Properties = ()> + 'static,
     {
-        fs::create_dir_all(&self.config.output_dir)?;
+        fs::create_dir_all(&self.config.output_dir).with_path(&self.config.output_dir)?;
 
-        let path_prefix = std::env::var("YEW_SSG_CURRENT_PATH_PREFIX").unwrap_or_default();
+        let path_prefix = self.path_prefix();
         if !path_prefix.is_empty() {
             info!("Using path prefix: {}", path_prefix);
         }
 
-        for route in R::iter() {
-            let route_path = route.to_path();
-            info!("Generating route: {}", route_path);
+        self.render_jobs::<C>(self.plan_routes::<R>(&path_prefix), routes)
+            .await
+    }
 
-            // 1. Set YEW_SSG_CURRENT_PATH
-            if path_prefix.is_empty() {
-                unsafe { std::env::set_var("YEW_SSG_CURRENT_PATH", &route_path) };
-            } else {
-                let prefixed_path = if path_prefix.starts_with('/') {
-                    format!("{}{}", path_prefix, route_path)
-                } else {
-                    format!("/{}{}", path_prefix, route_path)
+    /// Generate parameterized routes based on configuration.
+    ///
+    /// Templates see the pages of this call as `routes`; use [`Self::build`] to list the
+    /// whole site.
+    pub async fn generate_parameterized_routes<R, C>(&self) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        self.start_run()?;
+        self.render_parameterized_routes::<R, C>(None).await
+    }
+
+    /// Render the pages of [`Self::generate_parameterized_routes`] within the current run,
+    /// listing `routes` (or the pages of this call) to the templates.
+    async fn render_parameterized_routes<R, C>(
+        &self,
+        routes: Option<&Value>,
+    ) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        info!("Generating parameterized routes from configuration...");
+
+        if self.config.route_params.is_empty() {
+            info!("No parameterized routes defined in configuration");
+            return Ok(GenerationReport::default());
+        }
+
+        let path_prefix = self.path_prefix();
+        if !path_prefix.is_empty() {
+            info!(
+                "Using path prefix for parameterized routes: {}",
+                path_prefix
+            );
+        }
+
+        let mut issues = Vec::new();
+        let jobs = self.plan_parameterized_routes::<R>(&path_prefix, &mut issues);
+        // Parameter combinations no route recognizes fail like a route would
+        let mut unrecognized = Vec::new();
+        for issue in issues {
+            if let PlanIssue::UnrecognizedParams { pattern, path } = issue {
+                let error = SsgError::RouteRecognition {
+                    path: path.clone(),
+                    pattern: Some(pattern),
                 };
-                unsafe { std::env::set_var("YEW_SSG_CURRENT_PATH", &prefixed_path) };
-                info!("  Using prefixed path: {}", prefixed_path);
+                if self.config.failure_policy == FailurePolicy::FailFast {
+                    return Err(error);
+                }
+                unrecognized.push(PageFailure::new(&path, &error));
             }
+        }
 
-            // (Optional) If you know language externally, you could call set_current_language here.
+        let routes = routes.cloned().unwrap_or_else(|| self.routes_value(&jobs));
+        let mut report = self.render_jobs::<C>(jobs, &routes).await?;
+        if !unrecognized.is_empty() {
+            report.failures.extend(unrecognized);
+            report.failures.sort_by(|a, b| a.route.cmp(&b.route));
+        }
 
-            // 2. Render the root component (SSR)
-            let content = self.render_base_component::<C>().await?;
+        info!(
+            "Generated {} parameterized route pages in total",
+            report.pages.len()
+        );
+        Ok(report)
+    }
 
-            // 3. Clear path + language hints after render
-            unsafe { std::env::remove_var("YEW_SSG_CURRENT_PATH") };
-            Self::clear_current_language();
+    /// Copy the files of the configured asset pipeline into the output directory (below the
+    /// path prefix) and write `asset-manifest.json` next to them. Returns the written files.
+    pub fn copy_assets(&self) -> Result<Vec<PathBuf>, SsgError> {
+        let (Some(pipeline), Some(assets)) = (&self.config.assets, &self.asset_processor) else {
+            return Ok(Vec::new());
+        };
+        let dir = self
+            .config
+            .output_dir
+            .join(self.path_prefix().trim_matches('/'));
+        let written = assets.manifest().copy(pipeline, &dir)?;
+        info!(
+            "📦 Copied {} assets to {:?}",
+            assets.manifest().files.len(),
+            dir
+        );
+        Ok(written)
+    }
 
-            // 4. Metadata
-            let mut metadata = self.config.get_metadata_for_route(&route_path);
-            metadata.insert("path".to_string(), route_path.clone());
-            if !path_prefix.is_empty() {
-                metadata.insert("path_prefix".to_string(), path_prefix.clone());
+    /// Write `sitemap.xml` for the pages produced by [`Self::generate`] and
+    /// [`Self::generate_parameterized_routes`].
+    ///
+    /// URLs and `hreflang` alternates come from the configured `CanonicalLinkGenerator`
+    /// (or a default one), so a `domain` has to be set in the metadata or on the generator.
+    /// Returns the written files.
+    pub fn generate_sitemap<R>(&self) -> Result<Vec<PathBuf>, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+    {
+        let canonical = self.config.canonical_link_generator();
+        let base_url = self.config.site_domain().ok_or_else(|| {
+            SsgError::Config(
+                "Generating a sitemap requires a `domain` in the global metadata".to_string(),
+            )
+        })?;
+
+        let path_prefix = self.path_prefix();
+        let mut jobs = self.plan_routes::<R>(&path_prefix);
+        jobs.extend(self.plan_parameterized_routes::<R>(&path_prefix, &mut Vec::new()));
+
+        let sitemap = Sitemap::from_pages(&canonical, jobs.iter().map(|job| &job.metadata));
+        sitemap.write(&self.config.output_dir, &base_url)
+    }
+
+    /// Write `robots.txt` and the additional site files (e.g. `.well-known/security.txt`)
+    /// defined in the configuration. Returns the written files.
+    pub fn generate_site_files(&self) -> Result<Vec<PathBuf>, SsgError> {
+        let mut written = Vec::new();
+
+        if let Some(robots_txt) = &self.config.robots_txt {
+            let domain = self.config.site_domain();
+            if robots_txt.sitemap && domain.is_none() {
+                warn!("No `domain` configured, robots.txt is written without a Sitemap line");
             }
+            let path = self.config.output_dir.join(ROBOTS_TXT_FILE_NAME);
+            self.write_page(&path, &robots_txt.render(domain.as_deref()))?;
+            written.push(path);
+        }
 
-            // 5. Generator outputs
-            let mut generator_outputs = HashMap::new();
-            for generator in &self.config.generators.generators {
-                let name = generator.name();
-                let result = generator.generate(name, &route_path, &content, &metadata)?;
-                generator_outputs.insert(name.to_string(), result);
-
-                if let Some(support) = self.config.generators.try_get_output_support(generator) {
-                    for key in support.supported_outputs() {
-                        if key == name {
-                            continue;
-                        }
-                        if let Ok(extra) = generator.generate(key, &route_path, &content, &metadata)
-                        {
-                            generator_outputs.insert(key.to_string(), extra);
-                        }
-                    }
+        for (relative, content) in &self.config.site_files {
+            let path = self.config.output_dir.join(relative);
+            self.write_page(&path, content)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+
+    /// Write the RSS, Atom and JSON feeds configured in `config.feeds`.
+    ///
+    /// Items are the pages produced by [`Self::generate`] and
+    /// [`Self::generate_parameterized_routes`] that match a feed's pattern or prefix.
+    /// Returns the written files.
+    pub fn generate_feeds<R>(&self) -> Result<Vec<PathBuf>, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+    {
+        if self.config.feeds.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let domain = self.config.site_domain().ok_or_else(|| {
+            SsgError::Config(
+                "Generating feeds requires a `domain` in the global metadata".to_string(),
+            )
+        })?;
+        let domain = domain.trim_end_matches('/');
+        let default_language = self.config.canonical_link_generator().default_language;
+
+        let path_prefix = self.path_prefix();
+        let mut jobs = self.plan_routes::<R>(&path_prefix);
+        jobs.extend(self.plan_parameterized_routes::<R>(&path_prefix, &mut Vec::new()));
+
+        let mut written = Vec::new();
+        for feed_config in &self.config.feeds {
+            // Group the matching pages by feed language (None = default language)
+            let mut groups: BTreeMap<Option<String>, Vec<FeedItem>> = BTreeMap::new();
+            for job in jobs.iter().filter(|job| {
+                feed_config.matches(
+                    &job.route_path,
+                    job.metadata.get("lang").map(String::as_str),
+                )
+            }) {
+                let lang = job
+                    .metadata
+                    .get("lang")
+                    .filter(|lang| feed_config.per_language && **lang != default_language)
+                    .cloned();
+                let url = format!(
+                    "{}{}",
+                    domain,
+                    self.config.output_layout.href(&job.context.path)
+                );
+                groups
+                    .entry(lang)
+                    .or_default()
+                    .push(FeedItem::from_metadata(url, &job.metadata));
+            }
+
+            if groups.is_empty() {
+                warn!("No pages match the feed '{}'", feed_config.title);
+            }
+
+            for (lang, items) in groups {
+                let home_page_url = match &lang {
+                    Some(lang) => format!("{}/{}/", domain, lang),
+                    None => format!("{}/", domain),
+                };
+                let language = lang
+                    .clone()
+                    .or_else(|| feed_config.per_language.then(|| default_language.clone()));
+                let feed = Feed::new(feed_config, home_page_url, language, items);
+
+                for format in &feed_config.formats {
+                    let relative = feed_config.file_path(*format, lang.as_deref());
+                    let path = self.config.output_dir.join(&relative);
+                    let content = feed.render(*format, &format!("{}/{}", domain, relative));
+                    self.write_page(&path, &content)?;
+                    written.push(path);
                 }
             }
+        }
 
-            // 6. Processors
-            let processed_content = self.config.processors.process_all(
-                &content,
-                &metadata,
-                &generator_outputs,
-                &content,
-            )?;
+        Ok(written)
+    }
 
-            // 7. Final HTML assembly
-            let html = self.wrap_html(
-                &processed_content,
-                &route_path,
-                &metadata,
-                &generator_outputs,
-            )?;
+    /// Write the redirects configured in `config.redirects`.
+    ///
+    /// Every redirect becomes an HTML stub at its old path, named by the output layout, and
+    /// every configured host gets its redirect manifest. Stubs never replace pages written
+    /// earlier in the same run. Returns the written files.
+    pub fn generate_redirects(&self) -> Result<Vec<PathBuf>, SsgError> {
+        let redirects = &self.config.redirects;
+        if redirects.is_empty() {
+            return Ok(Vec::new());
+        }
 
-            // 8. Write file (respect prefix)
-            let (dir_path, file_path) = if path_prefix.is_empty() {
-                self.determine_output_path(&route_path)
-            } else {
-                let clean_prefix = path_prefix.trim_start_matches('/');
-                if route_path == "/" {
-                    let prefixed_dir = self.config.output_dir.join(clean_prefix);
-                    (prefixed_dir.clone(), prefixed_dir.join("index.html"))
-                } else {
-                    let component = route_path.trim_start_matches('/');
-                    let full = format!("{}/{}", clean_prefix, component);
-                    let dir = self.config.output_dir.join(full);
-                    (dir.clone(), dir.join("index.html"))
+        let path_prefix = self.path_prefix();
+        let base_path = match path_prefix.trim_matches('/') {
+            "" => String::new(),
+            prefix => format!("/{}", prefix),
+        };
+        let layout = &self.config.output_layout;
+
+        // Site paths including the path prefix and the trailing slash policy
+        let resolved = Redirects {
+            rules: redirects
+                .rules
+                .iter()
+                .map(|rule| Redirect {
+                    from: format!("{}{}", base_path, rule.from),
+                    to: if rule.is_external() {
+                        rule.to.clone()
+                    } else {
+                        format!("{}{}", base_path, layout.href(&rule.to))
+                    },
+                    status: rule.status,
+                })
+                .collect(),
+            hosts: redirects.hosts.clone(),
+        };
+
+        let domain = self.config.site_domain();
+        let mut stubs = Vec::new();
+        for (rule, resolved) in redirects.rules.iter().zip(&resolved.rules) {
+            let target = match &domain {
+                Some(domain) if !rule.is_external() => {
+                    format!("{}{}", domain.trim_end_matches('/'), resolved.to)
                 }
+                _ => resolved.to.clone(),
             };
+            let html = stub_page(&target);
+            for file_path in self.page_output_files(&rule.from, &path_prefix) {
+                stubs.push((file_path, resolved.from.as_str(), html.clone()));
+            }
+        }
+        self.claim_files(
+            stubs
+                .iter()
+                .map(|(file_path, route, _)| (file_path.clone(), *route)),
+        )?;
 
-            fs::create_dir_all(&dir_path)?;
-            fs::write(&file_path, html)?;
-            info!("  -> Saved to {:?}", file_path);
+        let mut written = Vec::new();
+        for (file_path, _, html) in stubs {
+            self.write_page(&file_path, &html)?;
+            written.push(file_path);
+        }
+        for host in &redirects.hosts {
+            let path = self.config.output_dir.join(host.file_name());
+            self.write_page(&path, &resolved.render_host(*host))?;
+            written.push(path);
         }
 
-        Ok(())
+        Ok(written)
     }
 
-    /// Generate parameterized routes based on configuration.
-    pub async fn generate_parameterized_routes<R, C>(&self) -> Result<(), Box<dyn Error>>
+    /// Check the internal links of the pages in the output directory.
+    ///
+    /// Uses `config.link_check` (or the defaults) and should run after all outputs
+    /// (pages, feeds, redirects, ...) are written. Broken links and orphan pages are logged;
+    /// with `fail_on_broken` set, broken links fail with [`SsgError::BrokenLinks`].
+    pub fn check_links(&self) -> Result<LinkReport, SsgError> {
+        let link_check = self.config.link_check.clone().unwrap_or_default();
+        let report = link_check.check(
+            &self.config.output_dir,
+            self.config.site_domain().as_deref(),
+        )?;
+
+        for broken in &report.broken {
+            warn!("Broken link: {}", broken);
+        }
+        for orphan in &report.orphans {
+            warn!(
+                "Orphan page: {} is not linked from any page",
+                orphan.display()
+            );
+        }
+        info!(
+            "Checked {} links in {} pages, {} broken",
+            report.links,
+            report.pages,
+            report.broken.len()
+        );
+
+        if link_check.fail_on_broken {
+            report.into_result()
+        } else {
+            Ok(report)
+        }
+    }
+
+    /// Write gzip and brotli siblings of the files in the output directory.
+    ///
+    /// Uses `config.precompress` and does nothing when it is not set. Should run after all
+    /// outputs are written; store the result in [`GenerationReport::compressed`] to include
+    /// it in the build report.
+    pub fn compress_outputs(&self) -> Result<Vec<CompressedFile>, SsgError> {
+        let Some(precompress) = &self.config.precompress else {
+            return Ok(Vec::new());
+        };
+        let compressed = precompress.compress_dir(&self.config.output_dir)?;
+
+        info!(
+            "Compressed {} files, saving up to {} bytes",
+            compressed.len(),
+            compressed.iter().map(CompressedFile::savings).sum::<u64>()
+        );
+        Ok(compressed)
+    }
+
+    /// Plan a build of [`Self::generate`] and [`Self::generate_parameterized_routes`]
+    /// without rendering anything.
+    ///
+    /// The plan lists the output file of every page and flags output collisions,
+    /// routes that do not round-trip through `R::recognize` and parameter combinations
+    /// no route recognizes.
+    pub fn plan<R>(&self) -> BuildPlan
     where
-        R: Routable + Clone + PartialEq + Debug + Send + 'static,
-        C: BaseComponent<Properties = ()> + 'static,
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
     {
-        info!("Generating parameterized routes from configuration...");
+        let path_prefix = self.path_prefix();
+        let mut plan = BuildPlan::default();
 
-        if self.config.route_params.is_empty() {
-            info!("No parameterized routes defined in configuration");
-            return Ok(());
+        for route in R::iter() {
+            let path = route.to_path();
+            let recognized = R::recognize(&path);
+            if recognized.as_ref() != Some(&route) {
+                plan.issues.push(PlanIssue::RoundTrip {
+                    path,
+                    recognized: recognized.map(|route| route.to_path()),
+                });
+            }
         }
 
-        let path_prefix = std::env::var("YEW_SSG_CURRENT_PATH_PREFIX").unwrap_or_default();
-        if !path_prefix.is_empty() {
-            info!(
-                "Using path prefix for parameterized routes: {}",
-                path_prefix
-            );
+        let mut jobs = self.plan_routes::<R>(&path_prefix);
+        jobs.extend(self.plan_parameterized_routes::<R>(&path_prefix, &mut plan.issues));
+
+        for job in jobs {
+            let mut files = self
+                .job_output_files(&job)
+                .into_iter()
+                .map(|file_path| self.relative_output_path(&file_path));
+            let output_file = files.next().unwrap_or_default();
+            plan.pages.push(PlannedPage {
+                route: job.context.path,
+                output_file,
+                copies: files.collect(),
+                pattern: job.pattern,
+                params: job.context.params,
+            });
         }
+        plan.detect_collisions();
 
-        let mut total_generated = 0;
+        for issue in &plan.issues {
+            warn!("Build plan: {}", issue);
+        }
+        plan
+    }
+
+    /// Collect a page for every variant of the route enum.
+    ///
+    /// Pages of the not-found route are marked `noindex` and written to `404.html`.
+    pub(crate) fn plan_routes<R>(&self, path_prefix: &str) -> Vec<PageJob>
+    where
+        R: Routable + IntoEnumIterator,
+    {
+        let not_found_path = R::not_found_route().map(|route| route.to_path());
+        let languages = self.config.languages();
+
+        R::iter()
+            .map(|route| {
+                let route_path = route.to_path();
+                let not_found = not_found_path.as_deref().is_some_and(|not_found_path| {
+                    is_not_found_path(&route_path, not_found_path, &languages)
+                });
+
+                let mut metadata = self.config.get_metadata_for_route(&route_path);
+                if not_found {
+                    let robots = metadata.get("robots").map_or("", String::as_str);
+                    metadata.insert("robots".to_string(), generators::with_noindex(robots));
+                }
+                metadata.insert(
+                    "path".to_string(),
+                    self.config.output_layout.href(&route_path),
+                );
+                if !path_prefix.is_empty() {
+                    metadata.insert("path_prefix".to_string(), path_prefix.to_string());
+                }
+
+                PageJob {
+                    not_found,
+                    ..PageJob::new(
+                        route_path,
+                        path_prefix,
+                        HashMap::new(),
+                        metadata,
+                        self.config.output_layout,
+                    )
+                }
+            })
+            .collect()
+    }
+
+    /// Collect a page for every parameter combination defined in the configuration.
+    ///
+    /// Combinations that no route recognizes, or that do not map back to the constructed
+    /// path, are recorded in `issues`.
+    pub(crate) fn plan_parameterized_routes<R>(
+        &self,
+        path_prefix: &str,
+        issues: &mut Vec<PlanIssue>,
+    ) -> Vec<PageJob>
+    where
+        R: Routable,
+    {
+        let mut jobs = Vec::new();
 
         for (pattern, route_params) in &self.config.route_params {
             let combos = route_params.generate_param_combinations();
@@ -248,121 +785,48 @@ impl StaticSiteGenerator {
 
                 if let Some(route) = R::recognize(&constructed_path) {
                     let route_path = route.to_path();
-                    info!(
-                        "Generating parameterized route: {} with params: {:?}",
-                        route_path, params
-                    );
-                    total_generated += 1;
-
-                    // 1. Set env path
-                    if path_prefix.is_empty() {
-                        unsafe { std::env::set_var("YEW_SSG_CURRENT_PATH", &route_path) };
-                    } else {
-                        let prefixed_path = if path_prefix.starts_with('/') {
-                            format!("{}{}", path_prefix, route_path)
-                        } else {
-                            format!("/{}{}", path_prefix, route_path)
-                        };
-                        unsafe { std::env::set_var("YEW_SSG_CURRENT_PATH", &prefixed_path) };
-                        info!("  Using prefixed path: {}", prefixed_path);
-                    }
-
-                    // Route param envs
-                    for (k, v) in &params {
-                        unsafe { std::env::set_var(format!("YEW_SSG_PARAM_{}", k), v) };
-                    }
-
-                    // 2. Render
-                    let content = self.render_base_component::<C>().await?;
-
-                    // 3. Clear env
-                    unsafe { std::env::remove_var("YEW_SSG_CURRENT_PATH") };
-                    for k in params.keys() {
-                        unsafe { std::env::remove_var(format!("YEW_SSG_PARAM_{}", k)) };
+                    if route_path != constructed_path {
+                        issues.push(PlanIssue::RoundTrip {
+                            path: constructed_path.clone(),
+                            recognized: Some(route_path.clone()),
+                        });
                     }
-                    Self::clear_current_language();
 
-                    // 4. Metadata
                     let mut metadata = self
                         .config
                         .get_metadata_for_parameterized_route(pattern, &params);
-                    metadata.insert("path".to_string(), route_path.clone());
+                    metadata.insert(
+                        "path".to_string(),
+                        self.config.output_layout.href(&route_path),
+                    );
                     if !path_prefix.is_empty() {
-                        metadata.insert("path_prefix".to_string(), path_prefix.clone());
-                    }
-
-                    // 5. Generators
-                    let mut generator_outputs = HashMap::new();
-                    for generator in &self.config.generators.generators {
-                        let name = generator.name();
-                        let main = generator.generate(name, &route_path, &content, &metadata)?;
-                        generator_outputs.insert(name.to_string(), main);
-
-                        if let Some(support) =
-                            self.config.generators.try_get_output_support(generator)
-                        {
-                            for key in support.supported_outputs() {
-                                if key == name {
-                                    continue;
-                                }
-                                if let Ok(out) =
-                                    generator.generate(key, &route_path, &content, &metadata)
-                                {
-                                    generator_outputs.insert(key.to_string(), out);
-                                }
-                            }
-                        }
+                        metadata.insert("path_prefix".to_string(), path_prefix.to_string());
                     }
 
-                    // 6. Processors
-                    let processed_content = self.config.processors.process_all(
-                        &content,
-                        &metadata,
-                        &generator_outputs,
-                        &content,
-                    )?;
-
-                    // 7. Final wrap
-                    let html = self.wrap_html(
-                        &processed_content,
-                        &route_path,
-                        &metadata,
-                        &generator_outputs,
-                    )?;
-
-                    // 8. Write
-                    let (dir_path, file_path) = if path_prefix.is_empty() {
-                        self.determine_output_path(&route_path)
-                    } else {
-                        let clean_prefix = path_prefix.trim_start_matches('/');
-                        if route_path == "/" {
-                            let prefixed_dir = self.config.output_dir.join(clean_prefix);
-                            (prefixed_dir.clone(), prefixed_dir.join("index.html"))
-                        } else {
-                            let comp = route_path.trim_start_matches('/');
-                            let full = format!("{}/{}", clean_prefix, comp);
-                            let dir = self.config.output_dir.join(full);
-                            (dir.clone(), dir.join("index.html"))
-                        }
-                    };
-
-                    fs::create_dir_all(&dir_path)?;
-                    fs::write(&file_path, html)?;
-                    info!("  -> Saved to {:?}", file_path);
+                    jobs.push(PageJob {
+                        pattern: Some(pattern.clone()),
+                        ..PageJob::new(
+                            route_path,
+                            path_prefix,
+                            params,
+                            metadata,
+                            self.config.output_layout,
+                        )
+                    });
                 } else {
                     warn!(
                         "No route recognized for constructed path: {} (pattern {})",
                         constructed_path, pattern
                     );
+                    issues.push(PlanIssue::UnrecognizedParams {
+                        pattern: pattern.clone(),
+                        path: constructed_path,
+                    });
                 }
             }
         }
 
-        info!(
-            "Generated {} parameterized route pages in total",
-            total_generated
-        );
-        Ok(())
+        jobs
     }
 
     // Helper function to construct a path by replacing placeholders with actual values
@@ -381,7 +845,23 @@ impl StaticSiteGenerator {
         &self,
         route_pattern: &str,
         route_builder: F,
-    ) -> Result<(), Box<dyn Error>>
+    ) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+        F: Fn(&HashMap<String, String>) -> R,
+    {
+        self.start_run()?;
+        self.render_with_params::<R, C, F>(route_pattern, route_builder)
+            .await
+    }
+
+    /// Render the pages of [`Self::generate_with_params`] within the current run.
+    async fn render_with_params<R, C, F>(
+        &self,
+        route_pattern: &str,
+        route_builder: F,
+    ) -> Result<GenerationReport, SsgError>
     where
         R: Routable + Clone + PartialEq + Debug + Send + 'static,
         C: BaseComponent<Properties = ()> + 'static,
@@ -391,7 +871,7 @@ impl StaticSiteGenerator {
             Some(p) => p,
             None => {
                 warn!("No parameters defined for route pattern: {}", route_pattern);
-                return Ok(());
+                return Ok(GenerationReport::default());
             }
         };
 
@@ -401,7 +881,7 @@ impl StaticSiteGenerator {
                 "No valid parameter combinations for route pattern: {}",
                 route_pattern
             );
-            return Ok(());
+            return Ok(GenerationReport::default());
         }
 
         info!(
@@ -410,125 +890,648 @@ impl StaticSiteGenerator {
             route_pattern
         );
 
+        let mut jobs = Vec::new();
         for params in combos {
             let route = route_builder(&params);
             let route_path = route.to_path();
-            info!(
-                "Generating parameterized route: {} with params: {:?}",
-                route_path, params
-            );
-
-            unsafe { std::env::set_var("YEW_SSG_CURRENT_PATH", &route_path) };
-            for (k, v) in &params {
-                unsafe { std::env::set_var(format!("YEW_SSG_PARAM_{}", k), v) };
-            }
-
-            let content = self.render_base_component::<C>().await?;
-
-            unsafe { std::env::remove_var("YEW_SSG_CURRENT_PATH") };
-            for k in params.keys() {
-                unsafe { std::env::remove_var(format!("YEW_SSG_PARAM_{}", k)) };
-            }
-            Self::clear_current_language();
 
             let mut metadata = self
                 .config
                 .get_metadata_for_parameterized_route(route_pattern, &params);
-            metadata.insert("path".to_string(), route_path.clone());
-
-            let mut generator_outputs = HashMap::new();
-            for generator in &self.config.generators.generators {
-                let name = generator.name();
-                let main = generator.generate(name, &route_path, &content, &metadata)?;
-                generator_outputs.insert(name.to_string(), main);
-
-                if let Some(support) = self.config.generators.try_get_output_support(generator) {
-                    for key in support.supported_outputs() {
-                        if key == name {
-                            continue;
-                        }
-                        if let Ok(extra) = generator.generate(key, &route_path, &content, &metadata)
-                        {
-                            generator_outputs.insert(key.to_string(), extra);
-                        }
-                    }
-                }
-            }
-
-            let processed_content = self.config.processors.process_all(
-                &content,
-                &metadata,
-                &generator_outputs,
-                &content,
-            )?;
-
-            let html = self.wrap_html(
-                &processed_content,
-                &route_path,
-                &metadata,
-                &generator_outputs,
-            )?;
+            metadata.insert(
+                "path".to_string(),
+                self.config.output_layout.href(&route_path),
+            );
 
-            let (dir_path, file_path) = self.determine_output_path(&route_path);
-            fs::create_dir_all(&dir_path)?;
-            fs::write(&file_path, html)?;
-            info!("  -> Saved to {:?}", file_path);
+            jobs.push(PageJob::new(
+                route_path,
+                "",
+                params,
+                metadata,
+                self.config.output_layout,
+            ));
         }
 
-        Ok(())
+        let routes = self.routes_value(&jobs);
+        self.render_jobs::<C>(jobs, &routes).await
     }
 
     /// Generate all pages with dynamic parameters defined in the configuration.
     pub async fn generate_all_parameterized_routes<R, C, F>(
         &self,
         route_builders: &HashMap<&str, F>,
-    ) -> Result<(), Box<dyn Error>>
+    ) -> Result<GenerationReport, SsgError>
     where
         R: Routable + Clone + PartialEq + Debug + Send + 'static,
         C: BaseComponent<Properties = ()> + 'static,
         F: Fn(&HashMap<String, String>) -> R + Clone, // Add Clone bound
     {
+        self.start_run()?;
+        let mut report = GenerationReport::default();
         for (route_pattern, route_builder) in route_builders {
             // Clone the route builder to avoid reference issues
             let builder_clone = route_builder.clone();
-            self.generate_with_params::<R, C, _>(route_pattern, builder_clone)
-                .await?;
+            report.merge(
+                self.render_with_params::<R, C, _>(route_pattern, builder_clone)
+                    .await?,
+            );
+        }
+        Ok(report)
+    }
+
+    /// Pages of a full build ([`Self::generate`] and [`Self::generate_parameterized_routes`]),
+    /// exposed to templates as `routes`.
+    fn site_routes<R>(&self, path_prefix: &str) -> Value
+    where
+        R: Routable + IntoEnumIterator,
+    {
+        let mut jobs = self.plan_routes::<R>(path_prefix);
+        jobs.extend(self.plan_parameterized_routes::<R>(path_prefix, &mut Vec::new()));
+        self.routes_value(&jobs)
+    }
+
+    /// Template value listing the given pages
+    pub(crate) fn routes_value(&self, jobs: &[PageJob]) -> Value {
+        let pages: Vec<PageInfo> = jobs
+            .iter()
+            .map(|job| {
+                PageInfo::new(
+                    &self.config,
+                    &job.route_path,
+                    &job.context.params,
+                    &job.metadata,
+                )
+            })
+            .collect();
+        Value::from_serialize(&pages)
+    }
+
+    /// Configured output path prefix (e.g. a GitHub Pages sub directory)
+    pub(crate) fn path_prefix(&self) -> String {
+        self.config.path_prefix.clone()
+    }
+
+    /// Render and write the given pages, keeping at most `config.concurrency` renders in flight.
+    ///
+    /// Each page is rendered from its own `SsgRenderContext`, so the output does not depend on
+    /// the order in which renders complete. In incremental mode pages whose inputs match the
+    /// build manifest are left untouched. Failing routes are handled by `config.failure_policy`.
+    async fn render_jobs<C>(
+        &self,
+        jobs: Vec<PageJob>,
+        routes: &Value,
+    ) -> Result<GenerationReport, SsgError>
+    where
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        let started = Instant::now();
+        let concurrency = self.config.concurrency.max(1);
+        if concurrency > 1 {
+            info!(
+                "Rendering {} routes with up to {} in parallel",
+                jobs.len(),
+                concurrency
+            );
+        }
+
+        self.claim_outputs(&jobs)?;
+
+        let mut manifest = self
+            .config
+            .incremental
+            .then(|| BuildManifest::load(&self.config.output_dir));
+        let (template, configuration) = if manifest.is_some() {
+            let (template, mut configuration) = self.hashed_configuration()?;
+            // Templates listing the other pages change whenever any page does
+            let uses_routes = self
+                .template_env
+                .templates()
+                .any(|(_, template)| template.undeclared_variables(false).contains("routes"));
+            if uses_routes {
+                configuration = format!("{}\n{}", configuration, routes);
+            }
+            (template, configuration)
+        } else {
+            Default::default()
+        };
+
+        let mut pages = stream::iter(jobs.into_iter().map(|job| async move {
+            info!("Generating route: {}", job.context.path);
+            let page = match self.before_route(&job) {
+                Ok(()) => {
+                    self.render_page::<C>(
+                        &job.route_path,
+                        job.context.clone(),
+                        &job.metadata,
+                        routes,
+                    )
+                    .await
+                }
+                Err(error) => Err(error),
+            };
+            (job, page)
+        }))
+        .buffer_unordered(concurrency);
+
+        let policy = self.config.failure_policy;
+        let mut report = GenerationReport::default();
+        while let Some((job, page)) = pages.next().await {
+            let page = page.and_then(|page| {
+                let page_report =
+                    self.store_page(&job, &page, manifest.as_mut(), &template, &configuration)?;
+                self.after_route(&job, page, page_report.status)?;
+                Ok(page_report)
+            });
+            match page {
+                Ok(page) => report.pages.push(page),
+                Err(error) if policy == FailurePolicy::FailFast => return Err(error),
+                Err(error) => {
+                    warn!("Failed to generate route {}: {}", job.context.path, error);
+                    report
+                        .failures
+                        .push(PageFailure::new(&job.context.path, &error));
+                }
+            }
+        }
+
+        if let Some(manifest) = manifest {
+            manifest.save(&self.config.output_dir)?;
+            info!("{} unchanged pages skipped", report.unchanged());
+        }
+
+        // Pages finish in any order; list them by route so the report is stable
+        report.pages.sort_by(|a, b| a.route.cmp(&b.route));
+        report.failures.sort_by(|a, b| a.route.cmp(&b.route));
+        report.total_time = started.elapsed();
+        if !report.is_success() {
+            warn!(
+                "{} of {} routes failed",
+                report.failures.len(),
+                report.pages.len() + report.failures.len()
+            );
+            if policy == FailurePolicy::ContinueAndReport {
+                return report.into_result();
+            }
+        }
+        Ok(report)
+    }
+
+    /// Write a rendered page unless the build manifest shows it unchanged.
+    fn store_page(
+        &self,
+        job: &PageJob,
+        page: &RenderedPage,
+        manifest: Option<&mut BuildManifest>,
+        template: &str,
+        configuration: &str,
+    ) -> Result<PageReport, SsgError> {
+        let files = self.job_output_files(job);
+        let file_path = files[0].clone();
+        let relative = self.relative_output_path(&file_path);
+
+        let mut status = PageStatus::Written;
+        let mut hash = None;
+        if let Some(manifest) = manifest.as_deref() {
+            // The route data is embedded in the page even if no component reads it
+            let content = match &page.data {
+                Some(data) => Cow::Owned(format!("{}\n{}", page.content, data)),
+                None => Cow::Borrowed(page.content.as_str()),
+            };
+            let page_hash = page_hash(&content, &job.metadata, template, configuration);
+            if manifest.is_unchanged(&job.context.path, &page_hash, &self.config.output_dir) {
+                info!("  -> Unchanged, skipping {:?}", file_path);
+                status = PageStatus::Unchanged;
+            } else {
+                hash = Some(page_hash);
+            }
+        }
+
+        if status == PageStatus::Written {
+            for file_path in &files {
+                self.write_page(file_path, &page.html)?;
+            }
+        }
+        // Only record pages once they are on disk
+        if let (Some(manifest), Some(hash)) = (manifest, hash) {
+            manifest.record(job.context.path.clone(), hash, relative.clone());
+        }
+
+        Ok(PageReport {
+            route: job.context.path.clone(),
+            output_file: relative,
+            bytes: page.html.len(),
+            render_time: page.render_time,
+            processing_time: page.processing_time,
+            status,
+            warnings: page.warnings.clone(),
+        })
+    }
+
+    /// Remove the pages of earlier builds that are no longer planned from the build manifest
+    /// and the output directory. Returns the removed files.
+    ///
+    /// Files written by another page of this run are kept.
+    fn remove_stale_pages<R>(&self) -> Result<Vec<PathBuf>, SsgError>
+    where
+        R: Routable + IntoEnumIterator,
+    {
+        let path_prefix = self.path_prefix();
+        let mut jobs = self.plan_routes::<R>(&path_prefix);
+        jobs.extend(self.plan_parameterized_routes::<R>(&path_prefix, &mut Vec::new()));
+        let planned: HashSet<&str> = jobs.iter().map(|job| job.context.path.as_str()).collect();
+
+        let mut manifest = BuildManifest::load(&self.config.output_dir);
+        let stale = manifest.retain_pages(&planned);
+        let written = self
+            .written_outputs
+            .lock()
+            .map_err(|_| SsgError::custom("Output registry is poisoned"))?;
+
+        let mut removed = Vec::new();
+        for file in stale {
+            let file_path = self.config.output_dir.join(&file);
+            if written.contains_key(&file_path) || !file_path.is_file() {
+                continue;
+            }
+            fs::remove_file(&file_path).with_path(&file_path)?;
+            info!("  -> Removed stale page {:?}", file_path);
+            // Drop the directories left empty, up to the output directory
+            let mut dir = file_path.parent();
+            while let Some(parent) = dir {
+                if parent == self.config.output_dir || fs::remove_dir(parent).is_err() {
+                    break;
+                }
+                dir = parent.parent();
+            }
+            removed.push(file);
+        }
+
+        manifest.save(&self.config.output_dir)?;
+        Ok(removed)
+    }
+
+    /// Run the plugins' `before_route` hooks for a page.
+    fn before_route(&self, job: &PageJob) -> Result<(), SsgError> {
+        if self.config.plugins.is_empty() {
+            return Ok(());
+        }
+        let page = PageInfo::new(
+            &self.config,
+            &job.route_path,
+            &job.context.params,
+            &job.metadata,
+        );
+        for plugin in &self.config.plugins {
+            plugin
+                .before_route(&self.config, &page)
+                .map_err(|error| error.in_plugin(Some(&job.context.path), plugin.name()))?;
+        }
+        Ok(())
+    }
+
+    /// Run the plugins' `after_route` hooks for a stored page and, during a build, keep it for
+    /// `after_build`.
+    fn after_route(
+        &self,
+        job: &PageJob,
+        page: RenderedPage,
+        status: PageStatus,
+    ) -> Result<(), SsgError> {
+        if self.config.plugins.is_empty() {
+            return Ok(());
+        }
+        let generated = GeneratedPage {
+            page: page.page,
+            output_file: self.relative_output_path(&self.job_output_files(job)[0]),
+            html: page.html,
+            status,
+        };
+        for plugin in &self.config.plugins {
+            plugin
+                .after_route(&self.config, &generated)
+                .map_err(|error| error.in_plugin(Some(&job.context.path), plugin.name()))?;
+        }
+        if let Some(pages) = self
+            .generated_pages
+            .lock()
+            .map_err(|_| SsgError::custom("Page registry is poisoned"))?
+            .as_mut()
+        {
+            pages.push(generated);
         }
         Ok(())
     }
 
-    /// Helper function to get current parameters during SSG
-    pub fn get_current_params() -> HashMap<String, String> {
-        let mut params = HashMap::new();
-        for (key, value) in std::env::vars() {
-            if let Some(param_name) = key.strip_prefix("YEW_SSG_PARAM_") {
-                params.insert(param_name.to_string(), value);
+    /// Replace the pages kept for `after_build`, returning the previous ones.
+    fn swap_generated_pages(
+        &self,
+        pages: Option<Vec<GeneratedPage>>,
+    ) -> Result<Option<Vec<GeneratedPage>>, SsgError> {
+        let mut generated = self
+            .generated_pages
+            .lock()
+            .map_err(|_| SsgError::custom("Page registry is poisoned"))?;
+        Ok(std::mem::replace(&mut *generated, pages))
+    }
+
+    /// Start a run, forgetting the output files written by earlier ones.
+    ///
+    /// [`Self::build`] and every `generate*` method start a run; other outputs, such as the
+    /// redirect stubs, are checked against the pages of the latest one.
+    fn start_run(&self) -> Result<(), SsgError> {
+        self.written_outputs
+            .lock()
+            .map_err(|_| SsgError::custom("Output registry is poisoned"))?
+            .clear();
+        Ok(())
+    }
+
+    /// Reserve the output files of the given pages for this run.
+    ///
+    /// Fails without writing anything if a page would overwrite a file written earlier in
+    /// the same run or by another page of the batch.
+    fn claim_outputs(&self, jobs: &[PageJob]) -> Result<(), SsgError> {
+        self.claim_files(jobs.iter().flat_map(|job| {
+            self.job_output_files(job)
+                .into_iter()
+                .map(|file_path| (file_path, job.context.path.as_str()))
+        }))
+    }
+
+    /// Reserve output files, given as `(file, route)` pairs, for this run.
+    fn claim_files<'a>(
+        &self,
+        files: impl IntoIterator<Item = (PathBuf, &'a str)>,
+    ) -> Result<(), SsgError> {
+        let mut written = self
+            .written_outputs
+            .lock()
+            .map_err(|_| SsgError::custom("Output registry is poisoned"))?;
+        let mut claimed: HashMap<PathBuf, &str> = HashMap::new();
+
+        for (file_path, route) in files {
+            let earlier = written
+                .get(&file_path)
+                .map(String::as_str)
+                .or_else(|| claimed.get(&file_path).copied());
+            if let Some(earlier) = earlier {
+                return Err(SsgError::OutputCollision {
+                    route: route.to_string(),
+                    output_file: file_path,
+                    existing_route: earlier.to_string(),
+                });
+            }
+            claimed.insert(file_path, route);
+        }
+
+        written.extend(
+            claimed
+                .into_iter()
+                .map(|(file_path, route)| (file_path, route.to_string())),
+        );
+        Ok(())
+    }
+
+    /// Output file relative to the output directory.
+    fn relative_output_path(&self, file_path: &Path) -> PathBuf {
+        file_path
+            .strip_prefix(&self.config.output_dir)
+            .unwrap_or(file_path)
+            .to_path_buf()
+    }
+
+    /// Template sources and generator/processor configuration hashed into the build manifest.
+    fn hashed_configuration(&self) -> Result<(String, String), SsgError> {
+        let templates: BTreeMap<_, _> = self
+            .template_env
+            .templates()
+            .map(|(name, template)| (name, template.source().to_string()))
+            .collect();
+        let template = format!("{:?}", templates);
+        let configuration = format!(
+            "{:?}\n{:?}\n{:?}\n{:?}\nhydration: {}",
+            self.config.generators,
+            self.config.processors,
+            self.config.template_functions.globals,
+            self.asset_processor.as_ref().map(AssetProcessor::manifest),
+            self.config.hydration
+        );
+        Ok((template, configuration))
+    }
+
+    /// Render a single page: route data, SSR, generators, processors and template wrapping.
+    pub(crate) async fn render_page<C>(
+        &self,
+        route_path: &str,
+        context: SsgRenderContext,
+        metadata: &HashMap<String, String>,
+        routes: &Value,
+    ) -> Result<RenderedPage, SsgError>
+    where
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        let page = PageInfo::new(&self.config, route_path, &context.params, metadata);
+
+        // 1. Load the route data handed to the components
+        let data = self.load_route_data(route_path, &context).await?;
+        let context = context.with_data(data.clone());
+
+        // 2. Render the root component (SSR), unless its markup is cached
+        let render_started = Instant::now();
+        let key = markup_key(&context, self.config.hydration);
+        let context_script = self.config.hydration.then(|| context_script(&context));
+        let cached = self
+            .markup_cache
+            .as_ref()
+            .and_then(|cache| cache.lock().ok()?.get(&key).cloned());
+        let content = match cached {
+            Some(content) => content,
+            None => {
+                let content = AssertUnwindSafe(self.render_base_component::<C>(context))
+                    .catch_unwind()
+                    .await
+                    .map_err(|payload| SsgError::ComponentPanic {
+                        route: route_path.to_string(),
+                        message: panic_message(payload.as_ref()),
+                    })??;
+                if let Some(cache) = &self.markup_cache
+                    && let Ok(mut cache) = cache.lock()
+                {
+                    cache.insert(key, content.clone());
+                }
+                content
             }
+        };
+        let render_time = render_started.elapsed();
+        let processing_started = Instant::now();
+
+        // 3. Generator outputs
+        let generator_outputs = self.run_generators(route_path, &content, metadata)?;
+
+        // 4. Processors
+        let mut processed_content =
+            self.config
+                .processors
+                .process_all(&content, metadata, &generator_outputs, &content)?;
+        if let Some(script) = context_script {
+            // Read back by `yew_router::hydrate` before hydrating the content
+            processed_content.push_str(&script);
+        }
+        if let Some(data) = &data {
+            // Read back by `yew_router::use_route_data` in the browser
+            processed_content.push_str(&route_data_script(data));
         }
-        params
+
+        // 5. Final HTML assembly
+        let mut warnings = Vec::new();
+        let html = self.render_template(
+            &processed_content,
+            &page,
+            metadata,
+            &generator_outputs,
+            routes,
+            &mut warnings,
+        )?;
+
+        Ok(RenderedPage {
+            page,
+            content,
+            data,
+            html,
+            render_time,
+            processing_time: processing_started.elapsed(),
+            warnings,
+        })
+    }
+
+    /// Load the data of a page with the first provider whose pattern matches its route.
+    ///
+    /// Patterns are matched with and without the language prefix of the route, so
+    /// `/crate/:id` also covers `/de/crate/:id`.
+    async fn load_route_data(
+        &self,
+        route_path: &str,
+        context: &SsgRenderContext,
+    ) -> Result<Option<String>, SsgError> {
+        let unprefixed = strip_lang_prefix(route_path, context.lang.as_deref());
+        let matched = self.config.route_data.iter().find_map(|route_data| {
+            let params = match_pattern(&route_data.pattern, route_path)
+                .or_else(|| unprefixed.and_then(|path| match_pattern(&route_data.pattern, path)))?;
+            Some((route_data, params))
+        });
+        let Some((route_data, params)) = matched else {
+            return Ok(None);
+        };
+
+        // Pages of plain routes have no parameters yet
+        let mut context = context.clone();
+        for (name, value) in params {
+            context.params.entry(name).or_insert(value);
+        }
+        let data = route_data
+            .provider
+            .load(&context)
+            .await
+            .and_then(|data| Ok(serde_json::to_string(&data)?))
+            .map_err(|error| error.in_route_data(route_path, &route_data.pattern))?;
+        Ok(Some(data))
+    }
+
+    /// Run all configured generators for a page, including their secondary outputs.
+    pub(crate) fn run_generators(
+        &self,
+        route_path: &str,
+        content: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, SsgError> {
+        let mut generator_outputs = HashMap::new();
+        for generator in &self.config.generators.generators {
+            let name = generator.name();
+            let result = generator
+                .generate(name, route_path, content, metadata)
+                .map_err(|error| error.in_generator(route_path, name))?;
+            generator_outputs.insert(name.to_string(), result);
+
+            if let Some(support) = self
+                .config
+                .generators
+                .try_get_output_support(generator.as_ref())
+            {
+                for key in support.supported_outputs() {
+                    if key == name {
+                        continue;
+                    }
+                    if let Ok(extra) = generator.generate(key, route_path, content, metadata) {
+                        generator_outputs.insert(key.to_string(), extra);
+                    }
+                }
+            }
+        }
+        Ok(generator_outputs)
+    }
+
+    /// Output files of a route following the output layout, primary file first.
+    ///
+    /// A non-empty path prefix places the files in a sub directory of the output directory.
+    fn page_output_files(&self, route_path: &str, path_prefix: &str) -> Vec<PathBuf> {
+        let base_dir = self.config.output_dir.join(path_prefix.trim_matches('/'));
+        self.config
+            .output_layout
+            .output_files(route_path)
+            .into_iter()
+            .map(|file| base_dir.join(file))
+            .collect()
+    }
+
+    /// Output files of a planned page; not-found pages go to `404.html` in their directory.
+    fn job_output_files(&self, job: &PageJob) -> Vec<PathBuf> {
+        if !job.not_found {
+            return self.page_output_files(&job.route_path, &job.path_prefix);
+        }
+
+        let dir = job
+            .route_path
+            .trim_matches('/')
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .unwrap_or_default();
+        vec![
+            self.config
+                .output_dir
+                .join(job.path_prefix.trim_matches('/'))
+                .join(dir)
+                .join(NOT_FOUND_FILE_NAME),
+        ]
+    }
+
+    /// Write a generated page to disk.
+    fn write_page(&self, file_path: &Path, html: &str) -> Result<(), SsgError> {
+        if let Some(dir_path) = file_path.parent() {
+            fs::create_dir_all(dir_path).with_path(dir_path)?;
+        }
+        fs::write(file_path, html).with_path(file_path)?;
+        info!("  -> Saved to {:?}", file_path);
+        Ok(())
     }
 
     /// Render the root component to HTML using server-side rendering.
-    async fn render_base_component<C>(&self) -> Result<String, Box<dyn Error>>
+    async fn render_base_component<C>(&self, context: SsgRenderContext) -> Result<String, SsgError>
     where
         C: BaseComponent<Properties = ()> + 'static,
     {
-        let renderer = ServerRenderer::<C>::new();
+        let renderer = ServerRenderer::<SsgRoot<C>>::with_props(move || SsgRootProps { context })
+            .hydratable(self.config.hydration);
         Ok(renderer.render().await)
     }
 
-    /// Determine output directory and file path for a route.
+    /// Determine output directory and primary file path for a route.
     pub fn determine_output_path(&self, route_path: &str) -> (PathBuf, PathBuf) {
-        if route_path == "/" {
-            (
-                self.config.output_dir.clone(),
-                self.config.output_dir.join("index.html"),
-            )
-        } else {
-            let path_component = route_path.trim_start_matches('/');
-            let dir = self.config.output_dir.join(path_component);
-            (dir.clone(), dir.join("index.html"))
-        }
+        let file_path = self.page_output_files(route_path, "").remove(0);
+        let dir = file_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| self.config.output_dir.clone());
+        (dir, file_path)
     }
 
     /// Create the final HTML by combining rendered content, metadata, and generator outputs.
@@ -538,9 +1541,64 @@ impl StaticSiteGenerator {
         path: &str,
         metadata: &HashMap<String, String>,
         generator_outputs: &HashMap<String, String>,
-    ) -> Result<String, Box<dyn Error>> {
-        let tmpl = self.template_env.get_template("base")?;
-        let mut context = HashMap::new();
+    ) -> Result<String, SsgError> {
+        let page = PageInfo::new(&self.config, path, &HashMap::new(), metadata);
+        self.render_template(
+            content,
+            &page,
+            metadata,
+            generator_outputs,
+            &Value::from(Vec::<Value>::new()),
+            &mut Vec::new(),
+        )
+    }
+
+    /// Layout wrapping a page, selected by the `layout` (or `template`) metadata key.
+    ///
+    /// A name without extension also matches `<name>.html`; pages without a layout use `base`.
+    fn page_template(
+        &self,
+        path: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Result<minijinja::Template<'_, '_>, SsgError> {
+        let name = metadata
+            .get("layout")
+            .or_else(|| metadata.get("template"))
+            .map(String::as_str)
+            .unwrap_or(BASE_TEMPLATE);
+
+        self.template_env
+            .get_template(name)
+            .or_else(|error| {
+                self.template_env
+                    .get_template(&format!("{}.html", name))
+                    .map_err(|_| error)
+            })
+            .map_err(|source| SsgError::TemplateRender {
+                route: path.to_string(),
+                source,
+            })
+    }
+
+    /// Like [`Self::wrap_html`], additionally collecting warnings for the generation report.
+    fn render_template(
+        &self,
+        content: &str,
+        page: &PageInfo,
+        metadata: &HashMap<String, String>,
+        generator_outputs: &HashMap<String, String>,
+        routes: &Value,
+        warnings: &mut Vec<String>,
+    ) -> Result<String, SsgError> {
+        let path = page.path.as_str();
+        let tmpl = self.page_template(path, metadata)?;
+
+        // Structured objects, shadowed by flat keys of the same name
+        let mut context = HashMap::from([
+            ("page", Value::from_serialize(page)),
+            ("outputs", Value::from_serialize(generator_outputs)),
+            ("routes", routes.clone()),
+        ]);
 
         // Create a struct to hold our values to ensure they live long enough
         struct TemplateContext {
@@ -575,32 +1633,210 @@ impl StaticSiteGenerator {
             template_context
                 .values
                 .insert("title".to_string(), fallback_title);
-            warn!("No title provided for route '{}'", path);
+            let warning = format!("No title provided for route '{}'", path);
+            warn!("{}", warning);
+            warnings.push(warning);
         }
 
         // Create the context with references to our stored values
         for (key, value) in &template_context.values {
-            context.insert(key.as_str(), value.as_str());
+            context.insert(key.as_str(), Value::from(value.as_str()));
         }
 
         // Render the template with Minijinja
-        let rendered_template = tmpl.render(context)?;
+        let rendered_template =
+            tmpl.render(context)
+                .map_err(|source| SsgError::TemplateRender {
+                    route: path.to_string(),
+                    source,
+                })?;
 
         // Apply processors to the rendered template
         let processed_html = self.config.processors.process_all(
             &rendered_template,
-            &metadata,
-            &generator_outputs,
+            metadata,
+            generator_outputs,
             content,
         )?;
 
-        Ok(processed_html)
+        // Point asset references at the copied (and possibly fingerprinted) files
+        match &self.asset_processor {
+            Some(assets) => assets
+                .process(&processed_html, metadata, generator_outputs, content)
+                .map_err(|error| error.in_processor(path, assets.name())),
+            None => Ok(processed_html),
+        }
+    }
+}
+
+/// Output of rendering a single page.
+pub(crate) struct RenderedPage {
+    /// Route, parameters, language and metadata of the page
+    page: PageInfo,
+    /// Markup produced by the root component
+    content: String,
+    /// Route data (JSON) the page was rendered with
+    data: Option<String>,
+    /// Final HTML written to disk
+    pub(crate) html: String,
+    /// Time spent rendering the component tree
+    render_time: Duration,
+    /// Time spent in generators, processors and the template
+    processing_time: Duration,
+    /// Warnings raised while assembling the page
+    warnings: Vec<String>,
+}
+
+/// Metadata key listing the metadata keys the components of a page read, comma separated
+const COMPONENT_METADATA_KEY: &str = "component_metadata";
+
+/// Cache key of the markup rendered for a render context
+///
+/// Metadata changes only re-wrap the cached markup, except for the keys a page lists in
+/// `component_metadata`: their values are part of the key.
+fn markup_key(context: &SsgRenderContext, hydratable: bool) -> String {
+    let metadata: BTreeMap<&str, &str> = context
+        .metadata
+        .get(COMPONENT_METADATA_KEY)
+        .map(|keys| {
+            keys.split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| (key, context.metadata.get(key).map_or("", String::as_str)))
+                .collect()
+        })
+        .unwrap_or_default();
+    format!(
+        "{}|{}|{:?}|{}|{:?}|{}",
+        context.path,
+        context.lang.as_deref().unwrap_or_default(),
+        context.layout,
+        hydratable,
+        metadata,
+        context.data.as_deref().unwrap_or_default()
+    )
+}
+
+/// Message of a caught panic
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Register every file below `dir` as a template named by its relative path (e.g. `docs.html`,
+/// `partials/nav.html`).
+fn load_templates_dir(env: &mut Environment<'static>, dir: &Path) -> Result<(), SsgError> {
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let mut entries = fs::read_dir(&current)
+            .with_path(&current)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .with_path(&current)?;
+        entries.sort();
+
+        for path in entries {
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let name = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let source = fs::read_to_string(&path).with_path(&path)?;
+            env.add_template_owned(name, source)
+                .map_err(|source| SsgError::TemplateLoad {
+                    path: Some(path.clone()),
+                    source: Box::new(source),
+                })?;
+        }
+    }
+    Ok(())
+}
+
+/// Error for a template that failed to load or compile
+fn template_load_error(source: minijinja::Error) -> SsgError {
+    SsgError::TemplateLoad {
+        path: None,
+        source: Box::new(source),
+    }
+}
+
+/// Whether `route_path` is the not-found route, either directly or below the prefix of one of
+/// the site's `languages` (e.g. `/de/404` for a localized route enum).
+fn is_not_found_path(route_path: &str, not_found_path: &str, languages: &[String]) -> bool {
+    if route_path == not_found_path {
+        return true;
+    }
+    if not_found_path == "/" {
+        return false;
+    }
+    route_path
+        .strip_suffix(not_found_path)
+        .and_then(|prefix| prefix.strip_prefix('/'))
+        .is_some_and(|lang| languages.iter().any(|language| language == lang))
+}
+
+/// A page scheduled for rendering.
+pub(crate) struct PageJob {
+    pub(crate) route_path: String,
+    pub(crate) path_prefix: String,
+    /// Route pattern for parameterized routes
+    pub(crate) pattern: Option<String>,
+    /// Page of the router's not-found route
+    pub(crate) not_found: bool,
+    pub(crate) context: SsgRenderContext,
+    pub(crate) metadata: HashMap<String, String>,
+}
+
+impl PageJob {
+    /// Prepare a page, building the render context handed to the root component.
+    pub(crate) fn new(
+        route_path: String,
+        path_prefix: &str,
+        params: HashMap<String, String>,
+        metadata: HashMap<String, String>,
+        layout: OutputLayout,
+    ) -> Self {
+        let (path, base_path) = if path_prefix.is_empty() {
+            (route_path.clone(), None)
+        } else {
+            let base_path = if path_prefix.starts_with('/') {
+                path_prefix.to_string()
+            } else {
+                format!("/{}", path_prefix)
+            };
+            (format!("{}{}", base_path, route_path), Some(base_path))
+        };
+
+        let context = SsgRenderContext::new(path)
+            .with_params(params)
+            .with_lang(metadata.get("lang").cloned())
+            .with_base_path(base_path)
+            .with_metadata(metadata.clone())
+            .with_layout(layout);
+
+        Self {
+            route_path,
+            path_prefix: path_prefix.to_string(),
+            pattern: None,
+            not_found: false,
+            context,
+            metadata,
+        }
     }
 }
 
 // Add to the bottom of yew-ssg/src/static_site_generator.rs
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::config::SsgConfigBuilder;
     use std::collections::HashMap;
@@ -938,11 +2174,8 @@ mod tests {
         let mut generator_outputs = HashMap::new();
         for generator_box in &generator.config.generators.generators {
             let key = generator_box.name();
-            match generator_box.generate(key, "/test", "", &metadata) {
-                Ok(output) => {
-                    generator_outputs.insert(key.to_string(), output);
-                }
-                Err(_) => {}
+            if let Ok(output) = generator_box.generate(key, "/test", "", &metadata) {
+                generator_outputs.insert(key.to_string(), output);
             }
 
             // Also get the canonical URL specifically
@@ -1177,4 +2410,312 @@ mod tests {
             "Canonical URL should be correctly generated for parameterized route"
         );
     }
+
+    #[derive(Clone, Routable, PartialEq, Debug, strum_macros::EnumIter)]
+    pub(crate) enum ContextRoute {
+        #[at("/")]
+        Home,
+        #[at("/crate/:id")]
+        Crate { id: String },
+    }
+
+    #[function_component(ContextProbe)]
+    pub(crate) fn context_probe() -> Html {
+        let path = yew_router::use_static_path().unwrap_or_default();
+        let params = yew_router::use_route_params();
+        let lang = yew_router::use_ssg_render_context()
+            .and_then(|context| context.lang)
+            .unwrap_or_default();
+        let id = params.get("id").cloned().unwrap_or_default();
+        html! { <p>{format!("path={} id={} lang={}", path, id, lang)}</p> }
+    }
+
+    #[tokio::test]
+    async fn test_generate_passes_render_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .default_template_string("{{ content | safe }}".to_string())
+            .route_metadata("/", HashMap::from([("lang".to_string(), "de".to_string())]))
+            .add_route_param("/crate/:id", "id", ["yew-ssg"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        generator
+            .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+
+        let home = fs::read_to_string(dir.path().join("index.html")).unwrap();
+        assert!(home.contains("path=/ id= lang=de"));
+
+        let crate_page = fs::read_to_string(dir.path().join("crate/yew-ssg/index.html")).unwrap();
+        assert!(crate_page.contains("path=/crate/yew-ssg id=yew-ssg lang=de"));
+        assert!(std::env::var("YEW_SSG_CURRENT_PATH").is_err());
+    }
+
+    fn collect_files(dir: &std::path::Path) -> Vec<(PathBuf, String)> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(collect_files(&path));
+            } else {
+                files.push((path.clone(), fs::read_to_string(&path).unwrap()));
+            }
+        }
+        files
+    }
+
+    #[tokio::test]
+    async fn test_parallel_output_matches_sequential() {
+        let mut outputs = Vec::new();
+        let mut reports = Vec::new();
+
+        for concurrency in [1, 4] {
+            let dir = tempfile::tempdir().unwrap();
+            let config = SsgConfigBuilder::new()
+                .output_dir(dir.path())
+                .add_route_param("/crate/:id", "id", ["a", "b", "c", "d", "e", "f"])
+                .concurrency(concurrency)
+                .build();
+            let generator = StaticSiteGenerator::new(config).unwrap();
+
+            let mut report = generator
+                .generate::<ContextRoute, ContextProbe>()
+                .await
+                .unwrap();
+            report.merge(
+                generator
+                    .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+                    .await
+                    .unwrap(),
+            );
+            reports.push(
+                report
+                    .pages
+                    .into_iter()
+                    .map(|page| (page.route, page.output_file, page.bytes, page.status))
+                    .collect::<Vec<_>>(),
+            );
+
+            let mut files: Vec<_> = collect_files(dir.path())
+                .into_iter()
+                .map(|(path, html)| (path.strip_prefix(dir.path()).unwrap().to_path_buf(), html))
+                .collect();
+            files.sort();
+            outputs.push(files);
+        }
+
+        assert_eq!(outputs[0].len(), 8);
+        assert_eq!(outputs[0], outputs[1]);
+
+        // The report lists the pages in the same order, whatever order they finished in
+        assert_eq!(reports[0], reports[1]);
+        let routes: Vec<_> = reports[1].iter().map(|page| page.0.as_str()).collect();
+        assert_eq!(
+            routes,
+            [
+                "/", "/crate/", "/crate/a", "/crate/b", "/crate/c", "/crate/d", "/crate/e",
+                "/crate/f"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_output_layout() {
+        use yew_router::{FileNaming, OutputLayout, TrailingSlash};
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com".to_string(),
+            )]))
+            .add_route_param("/crate/:id", "id", ["a"])
+            .output_layout(OutputLayout::new(FileNaming::Both, TrailingSlash::Always))
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let plan = generator.plan::<ContextRoute>();
+        let page = plan.page("/crate/a").unwrap();
+        assert_eq!(page.output_file, PathBuf::from("crate/a/index.html"));
+        assert_eq!(page.copies, vec![PathBuf::from("crate/a.html")]);
+        assert_eq!(
+            generator.determine_output_path("/crate/a").1,
+            dir.path().join("crate/a/index.html")
+        );
+
+        generator
+            .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let directory_index = fs::read_to_string(dir.path().join("crate/a/index.html")).unwrap();
+        let flat = fs::read_to_string(dir.path().join("crate/a.html")).unwrap();
+        assert_eq!(directory_index, flat);
+
+        generator.generate_sitemap::<ContextRoute>().unwrap();
+        let sitemap = fs::read_to_string(dir.path().join("sitemap.xml")).unwrap();
+        assert!(sitemap.contains("<loc>https://example.com/crate/a/</loc>"));
+    }
+
+    #[derive(Clone, Routable, PartialEq, Debug, strum_macros::EnumIter)]
+    enum NotFoundRoute {
+        #[at("/")]
+        Home,
+        #[not_found]
+        #[at("/404")]
+        NotFound,
+    }
+
+    #[test]
+    fn test_is_not_found_path() {
+        let languages = vec!["en".to_string(), "de".to_string()];
+        assert!(is_not_found_path("/404", "/404", &languages));
+        assert!(is_not_found_path("/de/404", "/404", &languages));
+        assert!(!is_not_found_path("/status/404", "/404", &languages));
+        assert!(!is_not_found_path("/fr/404", "/404", &languages));
+        assert!(!is_not_found_path("/blog/de/404", "/404", &languages));
+        assert!(!is_not_found_path("/x404", "/404", &languages));
+        assert!(!is_not_found_path("/de/", "/", &languages));
+    }
+
+    #[tokio::test]
+    async fn test_not_found_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SsgConfigBuilder::new().output_dir(dir.path()).build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+
+        let plan = generator.plan::<NotFoundRoute>();
+        assert_eq!(
+            plan.page("/404").unwrap().output_file,
+            PathBuf::from("404.html")
+        );
+
+        generator
+            .generate::<NotFoundRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let html = fs::read_to_string(dir.path().join("404.html")).unwrap();
+        assert!(html.contains(r#"<meta name="robots" content="noindex">"#));
+        assert!(!dir.path().join("404/index.html").exists());
+        assert!(dir.path().join("index.html").exists());
+
+        // Directives set for the page are kept next to `noindex`
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .route_metadata(
+                "/404",
+                HashMap::from([("robots".to_string(), "index, nofollow".to_string())]),
+            )
+            .build();
+        StaticSiteGenerator::new(config)
+            .unwrap()
+            .generate::<NotFoundRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let html = fs::read_to_string(dir.path().join("404.html")).unwrap();
+        assert!(html.contains(r#"<meta name="robots" content="noindex, nofollow">"#));
+    }
+
+    #[tokio::test]
+    async fn test_layouts_from_templates_dir() {
+        let templates = tempfile::tempdir().unwrap();
+        let files = [
+            ("layout.html", "<html>{% block body %}{% endblock %}</html>"),
+            (
+                "landing.html",
+                r#"{% extends "layout.html" %}{% block body %}<h1>Landing</h1>{{ content | safe }}{% endblock %}"#,
+            ),
+            (
+                "docs.html",
+                r#"{% extends "layout.html" %}{% block body %}{% include "partials/nav.html" %}{{ content | safe }}{% endblock %}"#,
+            ),
+            ("partials/nav.html", "<nav>Docs</nav>"),
+        ];
+        for (name, source) in files {
+            let path = templates.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let layout = |name: &str| HashMap::from([("layout".to_string(), name.to_string())]);
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .templates_dir(templates.path())
+            .route_metadata("/", layout("landing"))
+            .route_metadata("/crate/", layout("docs.html"))
+            .add_route_param("/crate/:id", "id", ["a"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        generator
+            .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+
+        let home = fs::read_to_string(dir.path().join("index.html")).unwrap();
+        assert!(home.starts_with("<html><h1>Landing</h1>"));
+        assert!(home.contains("<p>path=/ id="));
+
+        // The layout of `/crate/` is inherited by the pages below it
+        let docs = fs::read_to_string(dir.path().join("crate/a/index.html")).unwrap();
+        assert!(docs.starts_with("<html><nav>Docs</nav>"));
+        assert!(docs.contains("<p>path=/crate/a id=a"));
+
+        // Unknown layouts fail the page
+        let config = SsgConfigBuilder::new()
+            .output_dir(tempfile::tempdir().unwrap().path())
+            .named_template("base.html", "{{ content | safe }}")
+            .route_metadata("/", layout("missing"))
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        let err = generator
+            .generate::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SsgError::TemplateRender { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_hydration_embeds_render_context() {
+        for hydration in [false, true] {
+            let dir = tempfile::tempdir().unwrap();
+            let config = SsgConfigBuilder::new()
+                .output_dir(dir.path())
+                .default_template_string("<div id=\"app\">{{ content | safe }}</div>".to_string())
+                .route_metadata("/", HashMap::from([("lang".to_string(), "de".to_string())]))
+                .hydration(hydration)
+                .build();
+            let generator = StaticSiteGenerator::new(config).unwrap();
+            generator
+                .generate::<ContextRoute, ContextProbe>()
+                .await
+                .unwrap();
+
+            let home = fs::read_to_string(dir.path().join("index.html")).unwrap();
+            assert!(home.contains("path=/ id= lang=de"));
+            assert_eq!(home.contains("<!--<["), hydration);
+
+            let script = format!(r#"id="{}">"#, yew_router::CONTEXT_ELEMENT_ID);
+            let context = home.split_once(&script).map(|(_, rest)| {
+                let json = rest.split_once("</script>").unwrap().0;
+                yew_router::parse_context(json).unwrap()
+            });
+            assert_eq!(context.is_some(), hydration);
+            if let Some(context) = context {
+                assert_eq!(context.path, "/");
+                assert_eq!(context.lang.as_deref(), Some("de"));
+            }
+        }
+    }
 }
@@ -0,0 +1,182 @@
+//! Structured values handed to page templates.
+//!
+//! Besides the flat keys (`content`, `path`, every metadata key and generator output), each
+//! template sees:
+//!
+//! - `page`: the current page as a [`PageInfo`]
+//! - `site`: the site as a [`SiteInfo`]
+//! - `outputs`: generator outputs by key
+//! - `routes`: every page of the build as a list of [`PageInfo`] (only the pages of the call
+//!   for `generate_parameterized_routes` and `generate_with_params` on their own)
+//!
+//! Flat keys of the same name take precedence, so existing templates keep working.
+
+use crate::config::SsgConfig;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A page as seen by templates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageInfo {
+    /// Route path (e.g. `/crate/yew-ssg`)
+    pub path: String,
+    /// Link to the page, including the base path and the trailing slash policy
+    pub url: String,
+    /// Language of the page (the default language if none is set)
+    pub lang: String,
+    /// Route parameters
+    pub params: BTreeMap<String, String>,
+    /// Merged metadata of the page
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl PageInfo {
+    /// Describe a page of the given site
+    pub fn new(
+        config: &SsgConfig,
+        path: &str,
+        params: &HashMap<String, String>,
+        metadata: &HashMap<String, String>,
+    ) -> Self {
+        let base_path = match metadata
+            .get("path_prefix")
+            .map(|prefix| prefix.trim_matches('/'))
+        {
+            Some(prefix) if !prefix.is_empty() => format!("/{}", prefix),
+            _ => String::new(),
+        };
+
+        Self {
+            path: path.to_string(),
+            url: format!("{}{}", base_path, config.output_layout.href(path)),
+            lang: metadata
+                .get("lang")
+                .cloned()
+                .unwrap_or_else(|| config.canonical_link_generator().default_language),
+            params: params.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// The site as seen by templates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SiteInfo {
+    /// Site domain, if configured
+    pub domain: Option<String>,
+    /// Default language
+    pub default_language: String,
+    /// Languages of the site, from the `alternate_languages` global metadata
+    pub languages: Vec<String>,
+    /// Global metadata
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl SiteInfo {
+    /// Describe the site of a configuration
+    pub fn new(config: &SsgConfig) -> Self {
+        Self {
+            domain: config
+                .site_domain()
+                .map(|domain| domain.trim_end_matches('/').to_string()),
+            default_language: config.canonical_link_generator().default_language,
+            languages: config.languages(),
+            metadata: config
+                .global_metadata
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use crate::static_site_generator::StaticSiteGenerator;
+    use crate::static_site_generator::tests::{ContextProbe, ContextRoute};
+    use std::fs;
+
+    #[test]
+    fn test_page_and_site_info() {
+        let config = SsgConfigBuilder::new()
+            .global_metadata(HashMap::from([
+                ("domain".to_string(), "https://example.com/".to_string()),
+                ("alternate_languages".to_string(), "en, de".to_string()),
+            ]))
+            .build();
+
+        let site = SiteInfo::new(&config);
+        assert_eq!(site.domain.as_deref(), Some("https://example.com"));
+        assert_eq!(site.languages, vec!["en", "de"]);
+        assert_eq!(site.default_language, "en");
+
+        let page = PageInfo::new(
+            &config,
+            "/crate/a",
+            &HashMap::from([("id".to_string(), "a".to_string())]),
+            &HashMap::from([("path_prefix".to_string(), "repo".to_string())]),
+        );
+        assert_eq!(page.url, "/repo/crate/a");
+        assert_eq!(page.lang, "en");
+        assert_eq!(page.params["id"], "a");
+    }
+
+    #[tokio::test]
+    async fn test_structured_template_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = "{{ page.lang }}|{{ page.params.id }}|\
+            {% for route in routes %}{{ route.url }},{% endfor %}|\
+            {{ 'title' in outputs }}|{{ site.languages | join(',') }}|{{ path }}";
+        let config = SsgConfigBuilder::new()
+            .output_dir(dir.path())
+            .default_template_string(template.to_string())
+            .global_metadata(HashMap::from([(
+                "alternate_languages".to_string(),
+                "en,de".to_string(),
+            )]))
+            .add_route_param("/crate/:id", "id", ["a", "b"])
+            .build();
+        let generator = StaticSiteGenerator::new(config).unwrap();
+        generator
+            .build::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+
+        let routes_of = |page: &str| {
+            let mut routes: Vec<String> = page
+                .split('|')
+                .nth(2)
+                .unwrap()
+                .trim_end_matches(',')
+                .split(',')
+                .map(str::to_string)
+                .collect();
+            routes.sort();
+            routes
+        };
+        let page = fs::read_to_string(dir.path().join("crate/b/index.html")).unwrap();
+        let fields: Vec<&str> = page.split('|').collect();
+        assert_eq!(fields[0], "en");
+        assert_eq!(fields[1], "b");
+        assert_eq!(
+            routes_of(&page),
+            vec!["/", "/crate/", "/crate/a", "/crate/b"]
+        );
+        assert_eq!(fields[3], "True");
+        assert_eq!(fields[4], "en,de");
+        assert_eq!(fields[5], "/crate/b");
+
+        // On their own, the parameterized routes list the pages of the call
+        generator
+            .generate_parameterized_routes::<ContextRoute, ContextProbe>()
+            .await
+            .unwrap();
+        let page = fs::read_to_string(dir.path().join("crate/b/index.html")).unwrap();
+        assert_eq!(routes_of(&page), vec!["/crate/a", "/crate/b"]);
+    }
+}
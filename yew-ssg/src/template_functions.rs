@@ -0,0 +1,274 @@
+//! Filters, functions and globals available in page templates.
+//!
+//! Every template can use these built-ins, each as a function (`{{ asset_url("/app.js") }}`)
+//! and as a filter (`{{ "/app.js" | asset_url }}`):
+//!
+//! - `url(path)`: site path including the base path, following the output layout's
+//!   trailing slash policy
+//! - `asset_url(path)`: asset path including the base path, fingerprinted if the asset
+//!   pipeline renames it
+//! - `absolute_url(path)`: `url(path)` prefixed with the site domain
+//! - `localized_url(path[, lang])`: `url(path)` in the page's (or the given) language;
+//!   the default language has no language prefix
+//!
+//! The globals `site` (see [`SiteInfo`]), `site_domain` (if configured), `assets` (output paths
+//! by source path, if an asset pipeline is configured) and `default_language` are set as well.
+//! Custom filters, functions and globals are registered through `SsgConfigBuilder` and
+//! override built-ins of the same name.
+
+use crate::assets::AssetManifest;
+use crate::config::SsgConfig;
+use crate::template_context::SiteInfo;
+use minijinja::functions::Function;
+use minijinja::value::{FunctionArgs, FunctionResult, Rest};
+use minijinja::{Environment, State, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use yew_router::OutputLayout;
+
+/// Custom filters, functions and globals of the template environment
+#[derive(Debug, Clone, Default)]
+pub struct TemplateFunctions {
+    /// Filters by name
+    pub filters: BTreeMap<String, Value>,
+    /// Functions by name
+    pub functions: BTreeMap<String, Value>,
+    /// Global variables by name
+    pub globals: BTreeMap<String, Value>,
+}
+
+impl TemplateFunctions {
+    /// Register a filter (`{{ value | name(args) }}`)
+    pub fn add_filter<F, Rv, Args>(&mut self, name: &str, filter: F)
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        self.filters
+            .insert(name.to_string(), Value::from_function(filter));
+    }
+
+    /// Register a function (`{{ name(args) }}`)
+    pub fn add_function<F, Rv, Args>(&mut self, name: &str, function: F)
+    where
+        F: Function<Rv, Args>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        self.functions
+            .insert(name.to_string(), Value::from_function(function));
+    }
+
+    /// Register a global variable
+    pub fn add_global<V: Into<Value>>(&mut self, name: &str, value: V) {
+        self.globals.insert(name.to_string(), value.into());
+    }
+
+    /// Add the filters, functions and globals to an environment
+    pub fn register(&self, env: &mut Environment<'static>) {
+        for (name, filter) in &self.filters {
+            let filter = filter.clone();
+            env.add_filter(name.clone(), move |state: &State, args: Rest<Value>| {
+                filter.call(state, &args)
+            });
+        }
+        for (name, value) in self.functions.iter().chain(&self.globals) {
+            env.add_global(name.clone(), value.clone());
+        }
+    }
+}
+
+/// Register the built-in filters, functions and globals for a site
+pub(crate) fn register_builtins(
+    env: &mut Environment<'static>,
+    config: &SsgConfig,
+    assets: Option<&AssetManifest>,
+) {
+    let domain = config
+        .site_domain()
+        .map(|domain| domain.trim_end_matches('/').to_string());
+    let default_language = config.canonical_link_generator().default_language;
+    let layout = config.output_layout;
+
+    if let Some(domain) = &domain {
+        env.add_global("site_domain", domain.clone());
+    }
+    env.add_global("default_language", default_language.clone());
+    env.add_global("site", Value::from_serialize(SiteInfo::new(config)));
+    if let Some(assets) = assets {
+        env.add_global("assets", Value::from_serialize(&assets.files));
+    }
+
+    let url = move |state: &State, path: String| page_url(state, layout, &path);
+    env.add_function("url", url);
+    env.add_filter("url", url);
+
+    let assets = Arc::new(assets.cloned().unwrap_or_default());
+    let asset_url = move |state: &State, path: String| {
+        if is_external(&path) {
+            return path;
+        }
+        match assets.get(&path) {
+            Some(output) => format!("{}/{}", base_path(state), output),
+            None => format!("{}{}", base_path(state), path),
+        }
+    };
+    env.add_function("asset_url", asset_url.clone());
+    env.add_filter("asset_url", asset_url);
+
+    let absolute_url = move |state: &State, path: String| {
+        if is_external(&path) {
+            return path;
+        }
+        let url = page_url(state, layout, &path);
+        match &domain {
+            Some(domain) => format!("{}{}", domain, url),
+            None => url,
+        }
+    };
+    env.add_function("absolute_url", absolute_url.clone());
+    env.add_filter("absolute_url", absolute_url);
+
+    let localized_url = move |state: &State, path: String, lang: Option<String>| {
+        let lang = lang.or_else(|| {
+            state
+                .lookup("lang")
+                .and_then(|lang| lang.as_str().map(str::to_string))
+        });
+        let path = match lang {
+            Some(lang) if lang != default_language && path == "/" => format!("/{}", lang),
+            Some(lang) if lang != default_language => format!("/{}{}", lang, path),
+            _ => path,
+        };
+        page_url(state, layout, &path)
+    };
+    env.add_function("localized_url", localized_url.clone());
+    env.add_filter("localized_url", localized_url);
+}
+
+/// Site path with base path and trailing slash policy applied
+fn page_url(state: &State, layout: OutputLayout, path: &str) -> String {
+    if is_external(path) {
+        return path.to_string();
+    }
+    format!("{}{}", base_path(state), layout.href(path))
+}
+
+/// Base path of the current page (`/repo` for the path prefix `repo`), empty without prefix
+fn base_path(state: &State) -> String {
+    let prefix = state
+        .lookup("path_prefix")
+        .and_then(|prefix| prefix.as_str().map(str::to_string))
+        .unwrap_or_default();
+    match prefix.trim_matches('/') {
+        "" => String::new(),
+        prefix => format!("/{}", prefix),
+    }
+}
+
+fn is_external(path: &str) -> bool {
+    path.contains("://") || path.starts_with("//") || !path.starts_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SsgConfigBuilder;
+    use std::collections::HashMap;
+    use yew_router::{FileNaming, TrailingSlash};
+
+    fn environment(config: &SsgConfig) -> Environment<'static> {
+        let mut env = Environment::new();
+        register_builtins(&mut env, config, None);
+        config.template_functions.register(&mut env);
+        env
+    }
+
+    #[test]
+    fn test_builtin_functions() {
+        let config = SsgConfigBuilder::new()
+            .global_metadata(HashMap::from([(
+                "domain".to_string(),
+                "https://example.com/".to_string(),
+            )]))
+            .output_layout(OutputLayout::new(
+                FileNaming::DirectoryIndex,
+                TrailingSlash::Always,
+            ))
+            .build();
+        let env = environment(&config);
+
+        let render = |source: &str, lang: &str, prefix: &str| {
+            env.render_str(
+                source,
+                minijinja::context! { lang => lang, path_prefix => prefix },
+            )
+            .unwrap()
+        };
+
+        assert_eq!(render(r#"{{ url("/about") }}"#, "en", ""), "/about/");
+        assert_eq!(
+            render(r#"{{ "/app.js" | asset_url }}"#, "en", "repo"),
+            "/repo/app.js"
+        );
+        assert_eq!(
+            render(r#"{{ absolute_url("/about") }}"#, "en", ""),
+            "https://example.com/about/"
+        );
+        assert_eq!(
+            render(r#"{{ localized_url("/about") }}"#, "de", ""),
+            "/de/about/"
+        );
+        assert_eq!(
+            render(r#"{{ localized_url("/about", "en") }}"#, "de", ""),
+            "/about/"
+        );
+        assert_eq!(
+            render(
+                r#"{{ "https://cdn.example.com/x.js" | asset_url }}"#,
+                "en",
+                "repo"
+            ),
+            "https://cdn.example.com/x.js"
+        );
+        assert_eq!(render("{{ site_domain }}", "en", ""), "https://example.com");
+
+        let assets = AssetManifest {
+            files: BTreeMap::from([("app.js".to_string(), "app.3f9a1c.js".to_string())]),
+        };
+        let mut env = Environment::new();
+        register_builtins(&mut env, &config, Some(&assets));
+        let rendered = env
+            .render_str(
+                r#"{{ asset_url("/app.js") }} {{ assets["app.js"] }}"#,
+                minijinja::context! { path_prefix => "repo" },
+            )
+            .unwrap();
+        assert_eq!(rendered, "/repo/app.3f9a1c.js app.3f9a1c.js");
+    }
+
+    #[test]
+    fn test_custom_functions() {
+        let config = SsgConfigBuilder::new()
+            .template_filter("shout", |value: String| value.to_uppercase())
+            .template_function("t", |key: String| match key.as_str() {
+                "greeting" => Ok("Hello".to_string()),
+                _ => Err(minijinja::Error::new(
+                    minijinja::ErrorKind::InvalidOperation,
+                    format!("missing translation: {}", key),
+                )),
+            })
+            .template_global("year", 2025)
+            .build();
+        let env = environment(&config);
+
+        let rendered = env
+            .render_str(r#"{{ t("greeting") | shout }} {{ year }}"#, ())
+            .unwrap();
+        assert_eq!(rendered, "HELLO 2025");
+
+        let err = env.render_str(r#"{{ t("farewell") }}"#, ()).unwrap_err();
+        assert!(err.to_string().contains("missing translation: farewell"));
+    }
+}
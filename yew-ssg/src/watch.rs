@@ -0,0 +1,443 @@
+//! Watch mode: regenerate the site when its sources change.
+//!
+//! A [`SiteWatcher`] polls the configuration file, the template, the templates directory, the
+//! assets directories (JSON-LD files and the asset pipeline's source directory) and the content
+//! directories, and re-runs only the affected part of the pipeline:
+//!
+//! | Change | Rebuild |
+//! |--------|---------|
+//! | template, templates directory, assets | [`Rebuild::Rewrap`] |
+//! | configuration file | [`Rebuild::Reload`] |
+//! | content directories | [`Rebuild::Full`] |
+//!
+//! Re-wrapping runs generators, processors and templates again but reuses the markup the
+//! components rendered before, as long as the path, language and output layout of a page are
+//! unchanged. Pages whose components read metadata list those keys in the `component_metadata`
+//! metadata value (e.g. `tagline, title`) and are rendered again when one of them changes. Code
+//! changes need a restart of the watcher.
+
+use crate::config::SsgConfig;
+use crate::config_loader::load_config;
+use crate::error::SsgError;
+use crate::report::GenerationReport;
+use crate::static_site_generator::{MarkupCache, StaticSiteGenerator};
+use log::{error, info, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use strum::IntoEnumIterator;
+use yew::BaseComponent;
+use yew_router::Routable;
+
+/// Default time between two scans of the watched files
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Kind of a watched source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    /// The page template or a file in the templates directory
+    Template,
+    /// A file in an assets directory
+    Asset,
+    /// The configuration file
+    Config,
+    /// A file in a content directory
+    Content,
+}
+
+impl ChangeKind {
+    /// Part of the pipeline to run again after a change of this kind
+    pub fn rebuild(self) -> Rebuild {
+        match self {
+            ChangeKind::Template | ChangeKind::Asset => Rebuild::Rewrap,
+            ChangeKind::Config => Rebuild::Reload,
+            ChangeKind::Content => Rebuild::Full,
+        }
+    }
+}
+
+/// Part of the pipeline to run again, ordered from least to most work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rebuild {
+    /// Wrap the cached component markup of every page again
+    Rewrap,
+    /// Reload the configuration, wrap every page again and regenerate sitemap, site files,
+    /// feeds and redirects; only pages with a new path, language or layout, or a change in
+    /// their `component_metadata` keys, are rendered
+    Reload,
+    /// Render every page again and regenerate all other outputs
+    Full,
+}
+
+/// A changed, added or removed file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Kind of source the file belongs to
+    pub kind: ChangeKind,
+}
+
+/// Modification time and size of a file, compared between scans
+pub(crate) type FileStamp = (Option<SystemTime>, u64);
+
+/// Regenerates a site whenever its configuration, templates, assets or content change
+#[derive(Debug)]
+pub struct SiteWatcher {
+    config_path: PathBuf,
+    config: SsgConfig,
+    poll_interval: Duration,
+    files: BTreeMap<PathBuf, (ChangeKind, FileStamp)>,
+    markup: MarkupCache,
+}
+
+impl SiteWatcher {
+    /// Watch the site configured in `config_path` (YAML or JSON, see
+    /// [`load_config`](crate::config_loader::load_config))
+    pub fn new<P: AsRef<Path>>(config_path: P) -> Result<Self, SsgError> {
+        let config_path = config_path.as_ref().to_path_buf();
+        let config = load_config(&config_path)?;
+        let mut watcher = Self {
+            config_path,
+            config,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            files: BTreeMap::new(),
+            markup: MarkupCache::default(),
+        };
+        watcher.files = watcher.scan();
+        Ok(watcher)
+    }
+
+    /// Set the time between two scans of the watched files (default 500ms)
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Configuration of the current build
+    pub fn config(&self) -> &SsgConfig {
+        &self.config
+    }
+
+    /// Files and directories watched for the current configuration
+    pub fn watched_paths(&self) -> Vec<(PathBuf, ChangeKind)> {
+        let mut paths = vec![(self.config_path.clone(), ChangeKind::Config)];
+        paths.extend(source_paths(&self.config));
+        paths
+    }
+
+    /// Scan the watched files and return those changed, added or removed since the last scan
+    pub fn changes(&mut self) -> Vec<Change> {
+        let files = self.scan();
+        let mut changes: Vec<Change> = files
+            .iter()
+            .filter(|(path, entry)| self.files.get(*path) != Some(entry))
+            .chain(
+                self.files
+                    .iter()
+                    .filter(|(path, _)| !files.contains_key(*path)),
+            )
+            .map(|(path, (kind, _))| Change {
+                path: path.clone(),
+                kind: *kind,
+            })
+            .collect();
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        self.files = files;
+        changes
+    }
+
+    /// Run the given part of the pipeline, save the generation report and check the links if
+    /// `link_check` is configured. Compressed siblings are written if `precompress` is
+    /// configured
+    pub async fn rebuild<R, C>(&mut self, rebuild: Rebuild) -> Result<GenerationReport, SsgError>
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        if rebuild >= Rebuild::Reload {
+            self.config = load_config(&self.config_path)?;
+        }
+        if rebuild == Rebuild::Full
+            && let Ok(mut markup) = self.markup.lock()
+        {
+            markup.clear();
+        }
+
+        let generator =
+            StaticSiteGenerator::new(self.config.clone())?.with_markup_cache(self.markup.clone());
+        let mut report = generator.build::<R, C>().await?;
+
+        if rebuild >= Rebuild::Reload {
+            if self.config.site_domain().is_some() {
+                generator.generate_sitemap::<R>()?;
+            }
+            generator.generate_site_files()?;
+            generator.generate_feeds::<R>()?;
+            generator.generate_redirects()?;
+        }
+        report.compressed = generator.compress_outputs()?;
+        report.save(&self.config.output_dir)?;
+        if self.config.link_check.is_some() {
+            generator.check_links()?;
+        }
+        Ok(report)
+    }
+
+    /// Build the whole site, then rebuild whenever a watched file changes.
+    ///
+    /// Runs until the process is stopped. Failed builds are logged and the watcher keeps
+    /// going, so a broken configuration or template can be fixed without a restart.
+    pub async fn run<R, C>(mut self)
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        for (path, kind) in self.watched_paths() {
+            info!("👀 Watching {:?} ({:?})", path, kind);
+        }
+        self.rebuild_and_log::<R, C>(Rebuild::Full).await;
+
+        loop {
+            yew::platform::time::sleep(self.poll_interval).await;
+            let changes = self.changes();
+            let Some(rebuild) = changes.iter().map(|change| change.kind.rebuild()).max() else {
+                continue;
+            };
+            for change in &changes {
+                info!("✏️ {:?} changed ({:?})", change.path, change.kind);
+            }
+            self.rebuild_and_log::<R, C>(rebuild).await;
+        }
+    }
+
+    async fn rebuild_and_log<R, C>(&mut self, rebuild: Rebuild)
+    where
+        R: Routable + IntoEnumIterator + Clone + PartialEq + Debug + Send + 'static,
+        C: BaseComponent<Properties = ()> + 'static,
+    {
+        info!("🔁 Rebuilding ({:?})...", rebuild);
+        match self.rebuild::<R, C>(rebuild).await {
+            Ok(report) => {
+                for (route, warning) in report.warnings() {
+                    warn!("⚠️ {}: {}", route, warning);
+                }
+                info!(
+                    "✅ {} pages written, {} unchanged in {:?}",
+                    report.written(),
+                    report.unchanged(),
+                    report.total_time
+                );
+            }
+            Err(e) => error!("❌ Rebuild failed: {}", e),
+        }
+        // The build's own output (e.g. a template inside the output directory) must not
+        // trigger another rebuild, and a reloaded configuration may watch other paths
+        self.files = self.scan();
+    }
+
+    /// Stamps of all files below the watched paths
+    fn scan(&self) -> BTreeMap<PathBuf, (ChangeKind, FileStamp)> {
+        let mut files = BTreeMap::new();
+        for (path, kind) in self.watched_paths() {
+            let mut stamps = HashMap::new();
+            collect_stamps(&path, &mut stamps);
+            for (file, stamp) in stamps {
+                // Files watched twice (e.g. a template inside a content directory) keep the
+                // kind with the smaller rebuild
+                files
+                    .entry(file)
+                    .and_modify(|entry: &mut (ChangeKind, FileStamp)| entry.0 = entry.0.min(kind))
+                    .or_insert((kind, stamp));
+            }
+        }
+        files
+    }
+}
+
+/// Template, assets and content paths of a configuration
+pub(crate) fn source_paths(config: &SsgConfig) -> Vec<(PathBuf, ChangeKind)> {
+    let mut paths: Vec<_> = config
+        .template_path
+        .iter()
+        .chain(&config.templates_dir)
+        .map(|path| (path.clone(), ChangeKind::Template))
+        .collect();
+    paths.extend(
+        config
+            .assets_dir()
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(config.assets.iter().map(|assets| assets.source_dir.clone()))
+            .map(|dir| (dir, ChangeKind::Asset)),
+    );
+    paths.extend(
+        config
+            .content_dirs
+            .iter()
+            .map(|dir| (dir.clone(), ChangeKind::Content)),
+    );
+    paths.extend(
+        config
+            .route_data
+            .iter()
+            .filter_map(|route_data| route_data.provider.source_dir())
+            .map(|dir| (dir, ChangeKind::Content)),
+    );
+    paths
+}
+
+/// Record the stamp of `path`, or of every file below it if it is a directory
+pub(crate) fn collect_stamps(path: &Path, stamps: &mut HashMap<PathBuf, FileStamp>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            collect_stamps(&entry.path(), stamps);
+        }
+    } else {
+        stamps.insert(
+            path.to_path_buf(),
+            (metadata.modified().ok(), metadata.len()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use yew::prelude::*;
+
+    #[derive(Clone, Routable, PartialEq, Debug, strum_macros::EnumIter)]
+    enum WatchRoute {
+        #[at("/")]
+        Home,
+        #[at("/about")]
+        About,
+    }
+
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    #[function_component(RenderCounter)]
+    fn render_counter() -> Html {
+        RENDERS.fetch_add(1, Ordering::SeqCst);
+        html! { <p>{"component"}</p> }
+    }
+
+    /// Site with a config file, a template and an assets and a content directory
+    fn site(dir: &Path, tagline: &str) -> PathBuf {
+        fs::create_dir_all(dir.join("assets")).unwrap();
+        fs::create_dir_all(dir.join("content")).unwrap();
+        fs::write(dir.join("assets/site.jsonld"), "{}").unwrap();
+        fs::write(
+            dir.join("template.html"),
+            "<main>{{ content | safe }}</main>",
+        )
+        .unwrap();
+        let config = format!(
+            "general:\n  output_dir: {dist:?}\n  template_path: {template:?}\n  assets_base_dir: {assets:?}\n  content_dirs: [{content:?}]\nglobal_metadata:\n  tagline: {tagline:?}\n",
+            dist = dir.join("dist"),
+            template = dir.join("template.html"),
+            assets = dir.join("assets"),
+            content = dir.join("content"),
+        );
+        fs::write(dir.join("config.yaml"), config).unwrap();
+        dir.join("config.yaml")
+    }
+
+    #[test]
+    fn test_changes_are_classified() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut watcher = SiteWatcher::new(site(dir.path(), "Home")).unwrap();
+        assert_eq!(watcher.watched_paths().len(), 4);
+        assert!(watcher.changes().is_empty());
+
+        fs::write(dir.path().join("template.html"), "<div>{{ content }}</div>").unwrap();
+        fs::write(dir.path().join("content/post.md"), "# Post").unwrap();
+        fs::remove_file(dir.path().join("assets/site.jsonld")).unwrap();
+        let kinds: Vec<_> = watcher
+            .changes()
+            .into_iter()
+            .map(|change| change.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![ChangeKind::Asset, ChangeKind::Content, ChangeKind::Template]
+        );
+        assert!(watcher.changes().is_empty());
+
+        assert_eq!(ChangeKind::Template.rebuild(), Rebuild::Rewrap);
+        assert_eq!(ChangeKind::Config.rebuild(), Rebuild::Reload);
+        assert_eq!(ChangeKind::Content.rebuild(), Rebuild::Full);
+    }
+
+    #[tokio::test]
+    async fn test_rewrap_reuses_component_markup() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut watcher = SiteWatcher::new(site(dir.path(), "Home")).unwrap();
+        let index = dir.path().join("dist/index.html");
+
+        watcher
+            .rebuild::<WatchRoute, RenderCounter>(Rebuild::Full)
+            .await
+            .unwrap();
+        let renders = RENDERS.load(Ordering::SeqCst);
+        assert_eq!(renders, 2);
+
+        // Template changes re-wrap the cached markup
+        let template = "<h1>{{ tagline }}</h1><main>{{ content | safe }}</main>";
+        fs::write(dir.path().join("template.html"), template).unwrap();
+        watcher
+            .rebuild::<WatchRoute, RenderCounter>(Rebuild::Rewrap)
+            .await
+            .unwrap();
+        assert_eq!(RENDERS.load(Ordering::SeqCst), renders);
+        let html = fs::read_to_string(&index).unwrap();
+        assert!(html.starts_with("<h1>Home</h1><main>"));
+        assert!(html.contains("component"));
+
+        // Metadata changes only re-wrap the pages
+        site(dir.path(), "Welcome");
+        fs::write(dir.path().join("template.html"), template).unwrap();
+        let rebuild = watcher
+            .changes()
+            .iter()
+            .map(|change| change.kind.rebuild())
+            .max();
+        assert_eq!(rebuild, Some(Rebuild::Reload));
+        watcher
+            .rebuild::<WatchRoute, RenderCounter>(Rebuild::Reload)
+            .await
+            .unwrap();
+        assert_eq!(RENDERS.load(Ordering::SeqCst), renders);
+        let html = fs::read_to_string(&index).unwrap();
+        assert!(html.starts_with("<h1>Welcome</h1><main>"));
+
+        // Unless the components opt in to the changed keys
+        let config = fs::read_to_string(dir.path().join("config.yaml")).unwrap();
+        fs::write(
+            dir.path().join("config.yaml"),
+            format!("{}  component_metadata: tagline\n", config),
+        )
+        .unwrap();
+        watcher
+            .rebuild::<WatchRoute, RenderCounter>(Rebuild::Reload)
+            .await
+            .unwrap();
+        assert_eq!(RENDERS.load(Ordering::SeqCst), renders + 2);
+
+        // Content changes render the components again
+        watcher
+            .rebuild::<WatchRoute, RenderCounter>(Rebuild::Full)
+            .await
+            .unwrap();
+        assert_eq!(RENDERS.load(Ordering::SeqCst), renders + 4);
+    }
+}